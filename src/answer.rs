@@ -0,0 +1,139 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use num::BigInt;
+
+// The result of a puzzle part. Numeric variants compare by value regardless
+// of how they are stored, text answers sort after every number
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Big(BigInt),
+    Text(String),
+}
+
+impl Answer {
+    pub fn to_big(&self) -> Option<BigInt> {
+        match self {
+            Answer::Unsigned(n) => Some(BigInt::from(*n)),
+            Answer::Signed(n) => Some(BigInt::from(*n)),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl Ord for Answer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a.cmp(b),
+            (Answer::Signed(a), Answer::Signed(b)) => a.cmp(b),
+            (Answer::Text(a), Answer::Text(b)) => a.cmp(b),
+            (Answer::Text(_), _) => Ordering::Greater,
+            (_, Answer::Text(_)) => Ordering::Less,
+            (a, b) => a.to_big().cmp(&b.to_big()),
+        }
+    }
+}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Answer {}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Signed(value as i64)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("42", Answer::from(42_usize).to_string());
+        assert_eq!("-7", Answer::from(-7_i64).to_string());
+        assert_eq!("ABC", Answer::from("ABC").to_string());
+        let big = BigInt::from(u64::MAX) * 4;
+        assert_eq!("73786976294838206460", Answer::from(big).to_string());
+    }
+
+    #[test]
+    fn test_numeric_equality_across_variants() {
+        assert_eq!(Answer::from(42_usize), Answer::from(42_i64));
+        assert_eq!(Answer::from(42_i64), Answer::from(BigInt::from(42)));
+        assert_ne!(Answer::from(42_usize), Answer::from("42"));
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Answer::from(-1_i64) < Answer::from(0_usize));
+        assert!(Answer::from(u64::MAX) < Answer::from(BigInt::from(u64::MAX) + 1));
+        assert!(Answer::from(5_usize) < Answer::from("a"));
+        assert!(Answer::from("a") < Answer::from("b"));
+    }
+}
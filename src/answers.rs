@@ -0,0 +1,187 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use num::BigInt;
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+use crate::answer::Answer;
+
+// Known answers keyed by day and part, stored as a small TOML file:
+//
+// [day_01]
+// part_1 = 142
+// part_2 = "text answer"
+//
+// Numbers too big for a TOML integer are written as strings of digits
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(usize, usize), Answer>,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Answers> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read answers file [{}]", path))?;
+        text.parse()
+            .with_context(|| format!("Invalid answers file [{}]", path))
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn insert(&mut self, day: usize, part: usize, answer: Answer) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+// the file as TOML sees it, a table per day
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_1: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_2: Option<Value>,
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let tables: BTreeMap<String, DayAnswers> = toml::from_str(s)?;
+        let mut result = Answers::default();
+        for (name, table) in tables {
+            let day = name
+                .strip_prefix("day_")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| anyhow!("Expected a `[day_NN]` table, found [{}]", name))?;
+            for (part, value) in [(1, table.part_1), (2, table.part_2)] {
+                if let Some(value) = value {
+                    let answer = to_answer(value)
+                        .with_context(|| format!("Invalid answer for {}.part_{}", name, part))?;
+                    result.insert(day, part, answer);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn to_answer(value: Value) -> Result<Answer> {
+    match value {
+        Value::Integer(n) if n >= 0 => Ok(Answer::from(n as u64)),
+        Value::Integer(n) => Ok(Answer::from(n)),
+        Value::String(text) => Ok(match text.parse::<BigInt>() {
+            Ok(n) => Answer::from(n),
+            Err(_) => Answer::from(text.as_str()),
+        }),
+        other => Err(anyhow!("Expected a number or a string, found [{}]", other)),
+    }
+}
+
+fn to_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Unsigned(n) => i64::try_from(*n)
+            .map(Value::Integer)
+            .unwrap_or_else(|_| Value::String(n.to_string())),
+        Answer::Signed(n) => Value::Integer(*n),
+        Answer::Big(n) => Value::String(n.to_string()),
+        Answer::Text(s) => Value::String(s.clone()),
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tables: BTreeMap<String, DayAnswers> = BTreeMap::new();
+        for ((day, part), answer) in &self.answers {
+            let table = tables.entry(format!("day_{:02}", day)).or_default();
+            match part {
+                1 => table.part_1 = Some(to_value(answer)),
+                _ => table.part_2 = Some(to_value(answer)),
+            }
+        }
+        let text = toml::to_string(&tables).map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "# checked answers
+[day_01]
+part_1 = 142
+part_2 = 281 # from the example
+
+[day_18]
+part_2 = 952_408_144_115
+[day_25]
+part_1 = \"merry christmas\"
+";
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let answers: Answers = ANSWERS.parse()?;
+        assert_eq!(4, answers.len());
+        assert_eq!(Some(&Answer::from(142_u64)), answers.get(1, 1));
+        assert_eq!(Some(&Answer::from(281_u64)), answers.get(1, 2));
+        assert_eq!(None, answers.get(18, 1));
+        assert_eq!(Some(&Answer::from(952408144115_u64)), answers.get(18, 2));
+        assert_eq!(Some(&Answer::from("merry christmas")), answers.get(25, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let answers: Answers = ANSWERS.parse()?;
+        let text = answers.to_string();
+        assert!(text.starts_with("[day_01]\npart_1 = 142\n"));
+        assert_eq!(answers, text.parse()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_big_answers() -> Result<()> {
+        let mut answers = Answers::default();
+        answers.insert(3, 1, Answer::from(u64::MAX));
+        answers.insert(
+            3,
+            2,
+            "123456789012345678901234567890".parse::<BigInt>()?.into(),
+        );
+        let text = answers.to_string();
+        assert!(text.contains("part_1 = \"18446744073709551615\""));
+        assert_eq!(answers, text.parse()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        let err = "part_1 = 3".parse::<Answers>().unwrap_err();
+        assert!(err.to_string().contains("expected struct DayAnswers"));
+        let err = "[day_01]\npart_3 = 3".parse::<Answers>().unwrap_err();
+        assert!(err.to_string().contains("unknown field `part_3`"));
+        let err = "[day_01]\npart_1 = abc".parse::<Answers>().unwrap_err();
+        assert!(err.to_string().contains("line 2, column 10"));
+        let err = "[day_01]\npart_1 = 1.5".parse::<Answers>().unwrap_err();
+        assert_eq!(
+            "Expected a number or a string, found [1.5]",
+            err.root_cause().to_string()
+        );
+        let err = "[first]\npart_1 = 1".parse::<Answers>().unwrap_err();
+        assert_eq!(
+            "Expected a `[day_NN]` table, found [first]",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use advent_of_code::timing::{Stats, Timing, Timings};
+use advent_of_code::{days, AocError};
+
+use crate::cli::{Args, InputSource};
+use crate::{describe_input_error, read_day_input};
+
+const STATS_WIDTH: usize = 30;
+
+// times every selected day and part, returns false when any of them failed or
+// got slower than the baseline
+pub fn bench(args: &Args, runs: usize) -> bool {
+    let baseline = match args.baseline.as_deref().map(Timings::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return false;
+        }
+    };
+    let selected_days = args.days.days(&days());
+    if selected_days.len() > 1 && args.input == InputSource::Stdin {
+        eprintln!("Reading from stdin is only supported for a single day");
+        return false;
+    }
+    let mut is_ok = true;
+    let mut timings = Timings::default();
+    println!(
+        "Day  Part  {:<width$}  {:<width$}  Baseline parse / solve",
+        "Parse min / median / mean",
+        "Solve min / median / mean",
+        width = STATS_WIDTH
+    );
+    for day in selected_days {
+        let input = match read_day_input(&args.input, day) {
+            Ok(input) => input,
+            Err(e) => {
+                is_ok = false;
+                println!("{:>3}  error {}", day, describe_input_error(&e));
+                continue;
+            }
+        };
+        for part in &args.parts {
+            let timing = match Timing::measure(day, *part, &input, runs) {
+                Ok(timing) => timing,
+                Err(e) if matches!(e.downcast_ref(), Some(AocError::UnknownPart { .. })) => {
+                    continue
+                }
+                Err(e) => {
+                    is_ok = false;
+                    println!("{:>3}  {:>4}  error {:#}", day, part, e);
+                    continue;
+                }
+            };
+            let previous = baseline.as_ref().and_then(|b| b.find(day, *part));
+            let change_text = match previous {
+                Some(b) => {
+                    let change = format!(
+                        "{:+.1}% / {:+.1}%",
+                        timing.parse.change_from(&b.parse) * 100.0,
+                        timing.solve.change_from(&b.solve) * 100.0
+                    );
+                    if timing.is_regression_from(b) {
+                        is_ok = false;
+                        change + " SLOWER"
+                    } else {
+                        change
+                    }
+                }
+                None => String::new(),
+            };
+            let line = format!(
+                "{:>3}  {:>4}  {:<width$}  {:<width$}  {}",
+                day,
+                part,
+                format_stats(&timing.parse),
+                format_stats(&timing.solve),
+                change_text,
+                width = STATS_WIDTH
+            );
+            println!("{}", line.trim_end());
+            timings.timings.push(timing);
+        }
+    }
+    if let Some(path) = &args.save {
+        if let Err(e) = timings.save(path) {
+            eprintln!("{:#}", e);
+            is_ok = false;
+        }
+    }
+    is_ok
+}
+
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "{:.2?} / {:.2?} / {:.2?}",
+        Duration::from_nanos(stats.min),
+        Duration::from_nanos(stats.median),
+        Duration::from_nanos(stats.mean)
+    )
+}
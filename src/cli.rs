@@ -0,0 +1,301 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{bail, Context, Result};
+
+pub const USAGE: &str =
+    "Usage: advent_of_code [--day <N|N-M|all>] [--part <1|2>] [--input <PATH|->]
+                      [--verify [--answers <PATH>]] [--examples]
+                      [--bench <RUNS> [--save <PATH>] [--baseline <PATH>]]
+                      [--render] [--ppm <PATH>]
+
+Options:
+  -d, --day      day to run, a range of days like 1-12, or all (default: all)
+  -p, --part     part to run, both parts are run when omitted
+  -i, --input    input file, `-` reads stdin; `{day}` is replaced by the
+                 zero padded day number (default: inputs/day_{day}.txt)
+  -v, --verify   compare every answer with the answers file and fail on
+                 any mismatch
+  -a, --answers  answers file used by --verify (default: answers.toml)
+  -e, --examples run every day against its examples/day_NN_M.txt files and
+                 compare with the expected answers in examples/day_NN_M.toml
+  -b, --bench    run every part the given number of times and report
+                 parse and solve timings
+  --save         write the --bench timings as JSON to the given file
+  --baseline     compare the --bench timings with a saved JSON file and
+                 fail when a solve got slower
+  -r, --render   print a drawing of every part run, for the days that
+                 can draw one, like the day 16 beams or the day 18 trench
+  --ppm          save those drawings as PPM images; `{day}` and `{part}`
+                 are replaced in the path
+  -h, --help     print this message";
+
+const DEFAULT_INPUT: &str = "inputs/day_{day}.txt";
+const DEFAULT_ANSWERS: &str = "answers.toml";
+const DAY_PLACEHOLDER: &str = "{day}";
+const PART_PLACEHOLDER: &str = "{part}";
+
+#[derive(Debug, PartialEq)]
+pub enum DaySelection {
+    All,
+    Range(RangeInclusive<usize>),
+}
+
+impl DaySelection {
+    pub fn days(&self, available: &[usize]) -> Vec<usize> {
+        available
+            .iter()
+            .filter(|day| match self {
+                DaySelection::All => true,
+                DaySelection::Range(range) => range.contains(day),
+            })
+            .copied()
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(String),
+}
+
+impl InputSource {
+    pub fn for_day(&self, day: usize) -> InputSource {
+        match self {
+            InputSource::Stdin => InputSource::Stdin,
+            InputSource::File(path) => {
+                InputSource::File(path.replace(DAY_PLACEHOLDER, &format!("{:02}", day)))
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub days: DaySelection,
+    pub parts: Vec<usize>,
+    pub input: InputSource,
+    pub verify: bool,
+    pub examples: bool,
+    pub answers: String,
+    pub bench: Option<usize>,
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    pub render: bool,
+    pub ppm: Option<String>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            days: DaySelection::All,
+            parts: vec![1, 2],
+            input: InputSource::File(DEFAULT_INPUT.to_string()),
+            verify: false,
+            examples: false,
+            answers: DEFAULT_ANSWERS.to_string(),
+            bench: None,
+            save: None,
+            baseline: None,
+            render: false,
+            ppm: None,
+        }
+    }
+}
+
+// returns None when the usage text was requested
+pub fn parse_args(args: &[String]) -> Result<Option<Args>> {
+    let mut result = Args::default();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-d" | "--day" => result.days = parse_days(next_value(&mut it, arg)?)?,
+            "-p" | "--part" => result.parts = vec![parse_part(next_value(&mut it, arg)?)?],
+            "-i" | "--input" => result.input = parse_input(next_value(&mut it, arg)?),
+            "-v" | "--verify" => result.verify = true,
+            "-e" | "--examples" => result.examples = true,
+            "-a" | "--answers" => result.answers = next_value(&mut it, arg)?.to_string(),
+            "-b" | "--bench" => result.bench = Some(parse_runs(next_value(&mut it, arg)?)?),
+            "--save" => result.save = Some(next_value(&mut it, arg)?.to_string()),
+            "--baseline" => result.baseline = Some(next_value(&mut it, arg)?.to_string()),
+            "-r" | "--render" => result.render = true,
+            "--ppm" => result.ppm = Some(next_value(&mut it, arg)?.to_string()),
+            _ => bail!("Unknown argument [{}]", arg),
+        }
+    }
+    if result.bench.is_none() && (result.save.is_some() || result.baseline.is_some()) {
+        bail!("--save and --baseline need --bench");
+    }
+    if [result.bench.is_some(), result.verify, result.examples]
+        .iter()
+        .filter(|mode| **mode)
+        .count()
+        > 1
+    {
+        bail!("Only one of --bench, --verify and --examples can be used");
+    }
+    let is_drawing = result.render || result.ppm.is_some();
+    if is_drawing && (result.bench.is_some() || result.verify || result.examples) {
+        bail!("--render and --ppm only work when running days");
+    }
+    Ok(Some(result))
+}
+
+// where the drawing of a day part is saved
+pub fn image_path(template: &str, day: usize, part: usize) -> String {
+    template
+        .replace(DAY_PLACEHOLDER, &format!("{:02}", day))
+        .replace(PART_PLACEHOLDER, &part.to_string())
+}
+
+fn next_value<'a>(it: &mut std::slice::Iter<'a, String>, arg: &str) -> Result<&'a str> {
+    it.next()
+        .map(|s| s.as_str())
+        .with_context(|| format!("Missing value for [{}]", arg))
+}
+
+fn parse_days(text: &str) -> Result<DaySelection> {
+    if text == "all" {
+        return Ok(DaySelection::All);
+    }
+    let range = match text.split_once('-') {
+        Some((start, end)) => parse_day(start)?..=parse_day(end)?,
+        None => {
+            let day = parse_day(text)?;
+            day..=day
+        }
+    };
+    if range.is_empty() {
+        bail!("Invalid day range [{}]", text);
+    }
+    Ok(DaySelection::Range(range))
+}
+
+fn parse_day(text: &str) -> Result<usize> {
+    let day = text
+        .parse::<usize>()
+        .with_context(|| format!("Invalid day [{}]", text))?;
+    if !(1..=25).contains(&day) {
+        bail!("Day out of range [{}]", day);
+    }
+    Ok(day)
+}
+
+fn parse_part(text: &str) -> Result<usize> {
+    match text {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => bail!("Invalid part [{}]", text),
+    }
+}
+
+fn parse_runs(text: &str) -> Result<usize> {
+    match text.parse::<usize>() {
+        Ok(runs) if runs > 0 => Ok(runs),
+        _ => bail!("Invalid number of runs [{}]", text),
+    }
+}
+
+fn parse_input(text: &str) -> InputSource {
+    match text {
+        "-" => InputSource::Stdin,
+        path => InputSource::File(path.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_defaults() -> Result<()> {
+        let args = parse_args(&[])?.unwrap();
+        assert_eq!(Args::default(), args);
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_day_and_part() -> Result<()> {
+        let args = parse_args(&to_args("--day 19 --part 1 --input -"))?.unwrap();
+        assert_eq!(DaySelection::Range(19..=19), args.days);
+        assert_eq!(vec![1], args.parts);
+        assert_eq!(InputSource::Stdin, args.input);
+        Ok(())
+    }
+
+    #[test]
+    fn test_day_range() -> Result<()> {
+        let args = parse_args(&to_args("-d 13-15 -i day_{day}.txt"))?.unwrap();
+        assert_eq!(vec![13, 14, 15], args.days.days(&[1, 13, 14, 15, 16]));
+        assert_eq!(
+            InputSource::File("day_07.txt".to_string()),
+            args.input.for_day(7)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify() -> Result<()> {
+        let args = parse_args(&to_args("--verify -i inputs/{day}.txt"))?.unwrap();
+        assert!(args.verify);
+        assert_eq!("answers.toml", args.answers);
+        let args = parse_args(&to_args("-v -a checked.toml"))?.unwrap();
+        assert_eq!("checked.toml", args.answers);
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_input_is_per_day() -> Result<()> {
+        let args = parse_args(&to_args("--examples"))?.unwrap();
+        assert!(args.examples);
+        assert_eq!(
+            InputSource::File("inputs/day_03.txt".to_string()),
+            args.input.for_day(3)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bench() -> Result<()> {
+        let args = parse_args(&to_args("--bench 10 --save new.json --baseline old.json"))?.unwrap();
+        assert_eq!(Some(10), args.bench);
+        assert_eq!(Some("new.json".to_string()), args.save);
+        assert_eq!(Some("old.json".to_string()), args.baseline);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() -> Result<()> {
+        let args = parse_args(&to_args("-d 16 --render"))?.unwrap();
+        assert!(args.render);
+        assert_eq!(None, args.ppm);
+        let args = parse_args(&to_args("--ppm out/day_{day}_{part}.ppm"))?.unwrap();
+        assert!(!args.render);
+        let template = args.ppm.unwrap();
+        assert_eq!("out/day_18_2.ppm", image_path(&template, 18, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        assert!(parse_args(&to_args("--day 26")).is_err());
+        assert!(parse_args(&to_args("--day 5-3")).is_err());
+        assert!(parse_args(&to_args("--part 3")).is_err());
+        assert!(parse_args(&to_args("--day")).is_err());
+        assert!(parse_args(&to_args("--verbose")).is_err());
+        assert!(parse_args(&to_args("--verify --answers")).is_err());
+        assert!(parse_args(&to_args("--bench 0")).is_err());
+        assert!(parse_args(&to_args("--save out.json")).is_err());
+        assert!(parse_args(&to_args("--bench 3 --verify")).is_err());
+        assert!(parse_args(&to_args("--examples --verify")).is_err());
+        assert!(parse_args(&to_args("--verify --render")).is_err());
+        assert!(parse_args(&to_args("--bench 2 --ppm out.ppm")).is_err());
+        assert!(parse_args(&to_args("--ppm")).is_err());
+        assert!(parse_args(&to_args("--help")).unwrap().is_none());
+    }
+}
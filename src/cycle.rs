@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// A sequence of states built by repeatedly applying a step function ends up
+// repeating itself when there are finitely many states. `start` is the step
+// where the repetition begins, `len` how many steps it takes to come back and
+// `state` the state after the `n` steps that were asked for.
+//
+// The finders never return when the states never repeat.
+#[derive(Debug, PartialEq)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub len: usize,
+    pub state: S,
+}
+
+// step `n` of a sequence with the cycle [start, start + len) is the same as
+// this earlier step
+fn equivalent_step(n: usize, start: usize, len: usize) -> usize {
+    if n < start {
+        n
+    } else {
+        start + (n - start) % len
+    }
+}
+
+fn nth<S: Clone>(initial: &S, step: &mut impl FnMut(&S) -> S, n: usize) -> S {
+    let mut state = initial.clone();
+    for _ in 0..n {
+        state = step(&state);
+    }
+    state
+}
+
+// remembers every state, so it only walks the sequence once
+pub fn hashed<S, F>(initial: S, mut step: F, n: usize) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut state = initial;
+    loop {
+        if let Some(start) = seen.get(&state) {
+            let start = *start;
+            let len = states.len() - start;
+            let state = states[equivalent_step(n, start, len)].clone();
+            return Cycle { start, len, state };
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+// Floyd's tortoise and hare, it only keeps a couple of states around
+pub fn floyd<S, F>(initial: S, mut step: F, n: usize) -> Cycle<S>
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let half = step(&hare);
+        hare = step(&half);
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        len += 1;
+    }
+
+    let state = nth(&initial, &mut step, equivalent_step(n, start, len));
+    Cycle { start, len, state }
+}
+
+// Brent's variant, same memory as Floyd's with fewer steps
+pub fn brent<S, F>(initial: S, mut step: F, n: usize) -> Cycle<S>
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = nth(&initial, &mut step, len);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let state = nth(&initial, &mut step, equivalent_step(n, start, len));
+    Cycle { start, len, state }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, ..., 10, 4, 5, ..., 10, 4, ...
+    fn step(n: &usize) -> usize {
+        if *n < 10 {
+            n + 1
+        } else {
+            4
+        }
+    }
+
+    #[test]
+    fn test_finders_agree() {
+        for n in [0, 3, 4, 10, 11, 1_000_000_000] {
+            let expected = hashed(0, step, n);
+            assert_eq!(expected, floyd(0, step, n));
+            assert_eq!(expected, brent(0, step, n));
+        }
+    }
+
+    #[test]
+    fn test_hashed() {
+        let cycle = hashed(0, step, 1_000_000_000);
+        assert_eq!((4, 7), (cycle.start, cycle.len));
+        // 10^9 - 4 is 2 more than a multiple of 7
+        assert_eq!(6, cycle.state);
+        assert_eq!(3, hashed(0, step, 3).state);
+    }
+
+    #[test]
+    fn test_cycle_from_the_start() {
+        let cycle = brent(2, |n| (n + 1) % 3, 7);
+        assert_eq!(
+            Cycle {
+                start: 0,
+                len: 3,
+                state: 0
+            },
+            cycle
+        );
+        assert_eq!(cycle, floyd(2, |n| (n + 1) % 3, 7));
+    }
+}
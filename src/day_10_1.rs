@@ -1,6 +1,12 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::polygon;
+use crate::prelude::{Coord, Grid, Point};
+use crate::search;
+use crate::solution::{DayPart, Solution};
+use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 
 type Number = i64;
 type NumberPair = (Number, Number);
@@ -23,10 +29,14 @@ impl Position {
         self.1 == other.1 + 1
     }
     fn is_left(&self, other: &Position) -> bool {
-        self.0 == other.1 - 1
+        self.0 == other.0 - 1
     }
     fn is_right(&self, other: &Position) -> bool {
-        self.0 == other.1 + 1
+        self.0 == other.0 + 1
+    }
+
+    fn to_point(&self) -> Point {
+        Point::from(self.0, self.1)
     }
 }
 
@@ -42,9 +52,11 @@ enum PipeType {
     Start,
 }
 
-impl PipeType {
-    fn from_char(c: char) -> Self {
-        match c {
+impl TryFrom<char> for PipeType {
+    type Error = String;
+
+    fn try_from(c: char) -> std::result::Result<Self, Self::Error> {
+        Ok(match c {
             'S' => Self::Start,
             '|' => Self::NS,
             '-' => Self::WE,
@@ -52,9 +64,13 @@ impl PipeType {
             'J' => Self::NW,
             '7' => Self::SW,
             'F' => Self::SE,
-            _ => Self::None,
-        }
+            '.' => Self::None,
+            _ => return Err(format!("Unexpected char [{}]", c)),
+        })
     }
+}
+
+impl PipeType {
     fn get_deltas(&self) -> Vec<NumberPair> {
         match self {
             Self::None => vec![],
@@ -67,6 +83,32 @@ impl PipeType {
             Self::Start => vec![(0, 1), (0, -1), (1, 0), (-1, 0)],
         }
     }
+
+    fn glyph(&self) -> char {
+        match self {
+            Self::None => '.',
+            Self::NS => '│',
+            Self::NE => '└',
+            Self::NW => '┘',
+            Self::SE => '┌',
+            Self::SW => '┐',
+            Self::WE => '─',
+            Self::Start => 'S',
+        }
+    }
+
+    // heavier glyphs for the pipes of the loop
+    fn loop_glyph(&self) -> char {
+        match self {
+            Self::NS => '┃',
+            Self::NE => '┗',
+            Self::NW => '┛',
+            Self::SE => '┏',
+            Self::SW => '┓',
+            Self::WE => '━',
+            _ => self.glyph(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -84,7 +126,7 @@ impl Pipe {
             .get_deltas()
             .iter()
             .map(|delta_pair| (self.pos.0 + delta_pair.0, self.pos.1 + delta_pair.1))
-            .map(|pair| Position::from_pair_i64(pair))
+            .map(Position::from_pair_i64)
             .collect()
     }
 
@@ -93,86 +135,303 @@ impl Pipe {
     }
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut row = 0;
+// every pipe of the map, with the start tile replaced by the pipe it hides
+pub struct PipeMap {
+    graph: HashMap<Position, Pipe>,
+    start: Pipe,
+    width: usize,
+    height: usize,
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
+    type Parsed<'a> = PipeMap;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_pipes(&input.lines())
+    }
+
+    fn part_one(map: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(calculate_distances(&map.start, &map.graph).into())
+    }
+
+    fn part_two(map: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(count_enclosed(map)?.into())
+    }
+
+    fn render(map: &Self::Parsed<'_>, _part: DayPart) -> Result<Option<Grid<char>>> {
+        Ok(Some(draw_loop(map)))
+    }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    let map = parse_pipes(lines)?;
+    Ok(calculate_distances(&map.start, &map.graph))
+}
+
+fn parse_pipes(lines: &[&str]) -> Result<PipeMap> {
+    let grid: Grid<PipeType> = Grid::from_chars(lines)?;
     let mut graph: HashMap<Position, Pipe> = HashMap::new();
-    let mut starter_box: Option<Pipe> = None;
-    for line in lines {
-        for (column, c) in line.chars().enumerate() {
-            let pos = Position::from_pair((column, row));
-            let pipe_type = PipeType::from_char(c);
-            let pipe = Pipe::new(pos.clone(), pipe_type);
-            match pipe.pipe_type {
-                PipeType::Start => {
-                    starter_box = Some(pipe.clone());
-                    graph.insert(pos, pipe);
-                }
-                PipeType::None => drop(pipe),
-                _ => {
-                    graph.insert(pos, pipe);
-                }
-            };
-        }
-        row += 1;
+    let mut starter_pos: Option<Position> = None;
+    for (i, pipe_type) in grid.data.iter().enumerate() {
+        let coord = Coord::from_pos(i, grid.width);
+        let pos = Position::from_pair((coord.x, coord.y));
+        match pipe_type {
+            PipeType::Start => starter_pos = Some(pos),
+            PipeType::None => {}
+            _ => {
+                graph.insert(pos.clone(), Pipe::new(pos, pipe_type.clone()));
+            }
+        };
     }
-    let max_distance = calculate_distances(starter_box.unwrap(), &graph);
-    Ok(max_distance)
+    let starter_pos = starter_pos.ok_or_else(|| anyhow!("No start tile `S` in the input"))?;
+    let start = Pipe::new(starter_pos.clone(), infer_start_type(&starter_pos, &graph)?);
+    graph.insert(starter_pos, start.clone());
+    Ok(PipeMap {
+        graph,
+        start,
+        width: grid.width,
+        height: grid.height,
+    })
 }
 
-fn calculate_distances(starter: Pipe, graph: &HashMap<Position, Pipe>) -> usize {
-    //println!("Starter pipe [{:?}]", starter);
-    //println!("Calculating distances for graph [{:?}]", graph);
-    let mut queue: VecDeque<(&Pipe, usize)> = VecDeque::new();
-    queue.push_front((&starter, 0));
-    let mut visited: HashSet<&Position> = HashSet::new();
-    let mut max_distance: usize = 0;
-    while !queue.is_empty() {
-        let pair = queue.pop_back().unwrap();
-        let pipe = pair.0;
-        let pos = &pipe.pos;
-        if visited.contains(pos) {
-            continue;
-        }
-        let distance = pair.1 + 1;
-        visited.insert(pos);
-        let connections: Vec<&Pipe> = pipe
+// the start tile is the pipe connecting the two neighbours that connect to it
+fn infer_start_type(start: &Position, graph: &HashMap<Position, Pipe>) -> Result<PipeType> {
+    let connected: Vec<Position> = Pipe::new(start.clone(), PipeType::Start)
+        .get_connections()
+        .into_iter()
+        .filter(|pos| graph.get(pos).is_some_and(|pipe| pipe.is_connected(start)))
+        .collect();
+    let north = connected.iter().any(|pos| pos.is_above(start));
+    let south = connected.iter().any(|pos| pos.is_below(start));
+    let east = connected.iter().any(|pos| pos.is_right(start));
+    let west = connected.iter().any(|pos| pos.is_left(start));
+    Ok(match (north, south, east, west) {
+        (true, true, false, false) => PipeType::NS,
+        (true, false, true, false) => PipeType::NE,
+        (true, false, false, true) => PipeType::NW,
+        (false, true, true, false) => PipeType::SE,
+        (false, true, false, true) => PipeType::SW,
+        (false, false, true, true) => PipeType::WE,
+        _ => bail!(
+            "Start tile connects to {} pipes, expected 2",
+            connected.len()
+        ),
+    })
+}
+
+// the tiles of the loop in walking order, starting from the start tile
+fn find_loop(map: &PipeMap) -> Result<Vec<Position>> {
+    let start = &map.start.pos;
+    let mut path = vec![start.clone()];
+    let mut previous = start.clone();
+    let mut current = map.start.get_connections()[0].clone();
+    while current != *start {
+        let pipe = map
+            .graph
+            .get(&current)
+            .filter(|pipe| pipe.is_connected(&previous))
+            .ok_or_else(|| anyhow!("Loop is broken at {:?}", current))?;
+        let next = pipe
             .get_connections()
-            .iter()
-            .filter(|conn_pos| !visited.contains(conn_pos))
-            .map(|conn_pos| graph.get(conn_pos))
-            .filter(|opt| opt.is_some())
-            .map(|opt| opt.unwrap())
-            .collect();
-        //println!("Loop connections [{:?}] [{:?}]", pipe, connections);
-        for conn in connections {
-            if !conn.is_connected(pos) {
-                //println!("Skipping [{:?}] [{:?}]", conn, conn.get_connections());
-                continue;
-            }
+            .into_iter()
+            .find(|pos| *pos != previous)
+            .ok_or_else(|| anyhow!("Loop turns back at {:?}", current))?;
+        path.push(current);
+        previous = pipe.pos.clone();
+        current = next;
+    }
+    Ok(path)
+}
 
-            queue.push_front((&conn, distance));
-            if distance > max_distance {
-                max_distance = distance;
-            }
+// tiles strictly inside the loop, every tile of the loop is a corner of a
+// polygon whose lattice points are the tiles
+fn count_enclosed(map: &PipeMap) -> Result<usize> {
+    let vertices: Vec<Point> = find_loop(map)?.iter().map(Position::to_point).collect();
+    Ok(polygon::interior_points(&vertices) as usize)
+}
+
+// the loop drawn with heavy box-drawing glyphs, the pipes it does not use
+// are dimmed to light glyphs wherever they are, the ground it encloses is `I`
+// and the ground outside of it is `O`
+fn draw_loop(map: &PipeMap) -> Grid<char> {
+    let on_loop = loop_distances(&map.start, &map.graph);
+    let mut grid: Grid<char> = Grid::init(map.width, map.height, '.');
+    for y in 0..map.height {
+        // a row is inside the loop after crossing an odd number of pipes
+        // going north, so a ┌──┘ run crosses it once and a └──┘ run does not
+        let mut inside = false;
+        for x in 0..map.width {
+            let pos = Position::from_pair((x, y));
+            let glyph = match map.graph.get(&pos) {
+                Some(pipe) if on_loop.contains_key(&pos) => {
+                    if pipe.pipe_type.get_deltas().contains(&(0, -1)) {
+                        inside = !inside;
+                    }
+                    pipe.pipe_type.loop_glyph()
+                }
+                Some(pipe) => pipe.pipe_type.glyph(),
+                _ if inside => 'I',
+                _ => 'O',
+            };
+            grid.set(&Coord::from(x, y), glyph);
         }
     }
-    max_distance
+    grid
+}
+
+fn calculate_distances(starter: &Pipe, graph: &HashMap<Position, Pipe>) -> usize {
+    loop_distances(starter, graph)
+        .into_values()
+        .max()
+        .unwrap_or(0)
+}
+
+// steps from the start to every pipe of the loop
+fn loop_distances(starter: &Pipe, graph: &HashMap<Position, Pipe>) -> HashMap<Position, usize> {
+    //println!("Starter pipe [{:?}]", starter);
+    let successors = |pos: &Position| {
+        graph
+            .get(pos)
+            .map(|pipe| pipe.get_connections())
+            .unwrap_or_default()
+            .into_iter()
+            // both ends need to connect to each other
+            .filter(|conn_pos| {
+                graph
+                    .get(conn_pos)
+                    .is_some_and(|conn| conn.is_connected(pos))
+            })
+            .collect::<Vec<Position>>()
+    };
+    search::bfs([starter.pos.clone()], successors, |_| false).distances
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
+    use crate::solution::DayPart;
 
     #[test]
     fn test_simple_input() {
-        let input = " .....
+        let input = ".....
 .S-7.
 .|.|.
 .L-J.
 .....";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(4, result.unwrap());
     }
+
+    fn part_two(input: &str) -> Answer {
+        Day10::solve(&Input::from(input), DayPart::Two).unwrap()
+    }
+
+    #[test]
+    fn test_part_two_simple_input() {
+        let input = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        assert_eq!(Answer::from(4), part_two(input));
+        // squeezing between pipes does not leave the loop
+        let input = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+        assert_eq!(Answer::from(4), part_two(input));
+    }
+
+    #[test]
+    fn test_part_two_junk_pipes() {
+        let input = include_str!("../examples/day_10_1.txt");
+        assert_eq!(Answer::from(8), part_two(input));
+        let input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+        assert_eq!(Answer::from(10), part_two(input));
+    }
+
+    #[test]
+    fn test_infer_start_type() -> Result<()> {
+        let map = parse_pipes(&[".....", ".S-7.", ".|.|.", ".L-J.", "....."])?;
+        assert!(matches!(map.start.pipe_type, PipeType::SE));
+        assert_eq!(8, find_loop(&map)?.len());
+        let map = parse_pipes(&["-L|F7", "7S-7|", "L|7||", "-L-J|", "L|-JF"])?;
+        assert!(matches!(map.start.pipe_type, PipeType::SE));
+        assert!(parse_pipes(&["S-7", "|.|", "L-J"]).is_ok());
+        assert!(parse_pipes(&[".S-", "..."]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_sides() {
+        let pos = Position(3, 7);
+        assert!(Position(2, 7).is_left(&pos));
+        assert!(Position(4, 7).is_right(&pos));
+        assert!(!Position(6, 7).is_left(&pos));
+        assert!(Position(3, 6).is_above(&pos) && Position(3, 8).is_below(&pos));
+    }
+
+    #[test]
+    fn test_draw_loop() -> Result<()> {
+        let map = parse_pipes(&["-L|F7", "7S-7|", "L|7||", "-L-J|", "L|-JF"])?;
+        let expected = "─└│┌┐
+┐┏━┓│
+└┃┐┃│
+─┗━┛│
+└│─┘┌";
+        assert_eq!(expected, draw_loop(&map).to_string());
+
+        // ground squeezed between two pipes of the loop is still outside
+        let map = parse_pipes(
+            &include_str!("../examples/day_10_1.txt")
+                .lines()
+                .collect::<Vec<_>>(),
+        )?;
+        let expected = "O┏━━━━┓┏┓┏┓┏┓┏━┓OOOO
+O┃┏━━┓┃┃┃┃┃┃┃┃┏┛OOOO
+O┃┃O┏┛┃┃┃┃┃┃┃┃┗┓OOOO
+┏┛┗┓┗┓┗┛┗┛┃┃┗┛I┗━┓OO
+┗━━┛O┗┓III┗┛┏┓┏━┓┗┓O
+OOOO┏━┛II┏┓┏┛┃┗┓┗┓┗┓
+OOOO┗┓I┏┓┃┃┗┓┃I┗┓┗┓┃
+OOOOO┃┏┛┗┛┃┏┛┃┏┓┃O┗┛
+OOOO┏┛┗━┓O┃┃O┃┃┃┃OOO
+OOOO┗━━━┛O┗┛O┗┛┗┛OOO";
+        let drawing = draw_loop(&map);
+        assert_eq!(expected, drawing.to_string());
+        assert_eq!(count_enclosed(&map)?, drawing.count_eq(&'I'));
+
+        // junk pipes inside the loop keep their glyph
+        let map = parse_pipes(&["S---7", "|.-.|", "|...|", "L---J"])?;
+        let expected = "┏━━━┓
+┃I─I┃
+┃III┃
+┗━━━┛";
+        assert_eq!(expected, draw_loop(&map).to_string());
+        assert_eq!(6, count_enclosed(&map)?);
+        Ok(())
+    }
 }
@@ -1,4 +1,8 @@
+use crate::answer::Answer;
+use crate::day_11_2;
+use crate::input::Input;
 use crate::re_utils;
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 use anyhow::Result;
@@ -6,6 +10,8 @@ use anyhow::Result;
 type Number = i64;
 type NumberPair = (usize, usize);
 type Universe = Vec<Vec<Position>>;
+// the columns of the galaxies on every row of the image
+pub type Image = Vec<Vec<usize>>;
 
 #[derive(Clone, Eq, Hash, PartialEq, Debug)]
 struct Position {
@@ -46,29 +52,54 @@ impl Position {
 }
 
 fn diff(a: usize, b: usize) -> usize {
-    if a > b {
-        a - b
-    } else {
-        b - a
+    a.abs_diff(b)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+    type Parsed<'a> = Image;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_image(&input.lines())
     }
+
+    fn part_one(image: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(sum_distances(image).into())
+    }
+
+    fn part_two(image: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_11_2::sum_distances(image).into())
+    }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(sum_distances(&parse_image(lines)?))
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut universe: Universe = Vec::new();
+pub fn parse_image(lines: &[&str]) -> Result<Image> {
     let galaxy_char = '#';
-    for (line_no, line) in lines.iter().enumerate() {
-        let locations = re_utils::parse_loc(&line, &galaxy_char)?;
-        universe.push(
+    lines
+        .iter()
+        .map(|line| re_utils::parse_loc(line, &galaxy_char))
+        .collect()
+}
+
+fn sum_distances(image: &Image) -> usize {
+    let universe: Universe = image
+        .iter()
+        .enumerate()
+        .map(|(line_no, locations)| {
             locations
                 .iter()
                 .map(|x| Position::new(*x, line_no))
-                .collect(),
-        );
-    }
+                .collect()
+        })
+        .collect();
     let universe = expand_universe(universe);
     //println!("{:?}", universe);
-    let max_distance = sum_all_positions(&universe);
-    Ok(max_distance)
+    sum_all_positions(&universe)
 }
 
 fn sum_all_positions(all_positions: &Vec<Position>) -> usize {
@@ -97,10 +128,7 @@ fn expand_universe_columns(universe: Universe) -> Vec<Position> {
             if max_col < pos.column {
                 max_col = pos.column;
             }
-            universe_by_col
-                .entry(pos.column)
-                .or_insert(Vec::new())
-                .push(pos);
+            universe_by_col.entry(pos.column).or_default().push(pos);
         }
     }
     let mut augment: usize = 0;
@@ -136,7 +164,6 @@ fn expand_universe_rows(universe: Universe) -> Universe {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn test_simple_input() {
@@ -151,8 +178,9 @@ mod tests {
 .......#..
 #...#.....
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(374, result.unwrap());
     }
 
@@ -183,7 +211,7 @@ mod tests {
     }
 
     fn test_distance_single(a: &Position, b: &Position, expect: usize) {
-        assert_eq!(expect, a.distance(&b));
-        assert_eq!(a.distance(&b), b.distance(&a));
+        assert_eq!(expect, a.distance(b));
+        assert_eq!(a.distance(b), b.distance(a));
     }
 }
@@ -1,4 +1,4 @@
-use crate::re_utils;
+use crate::day_11_1::{parse_image, Image};
 use std::collections::HashMap;
 
 use anyhow::Result;
@@ -47,29 +47,27 @@ impl Position {
 }
 
 fn diff(a: usize, b: usize) -> usize {
-    if a > b {
-        a - b
-    } else {
-        b - a
-    }
+    a.abs_diff(b)
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(sum_distances(&parse_image(lines)?))
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut universe: Universe = Vec::new();
-    let galaxy_char = '#';
-    for (line_no, line) in lines.iter().enumerate() {
-        let locations = re_utils::parse_loc(&line, &galaxy_char)?;
-        universe.push(
+pub fn sum_distances(image: &Image) -> usize {
+    let universe: Universe = image
+        .iter()
+        .enumerate()
+        .map(|(line_no, locations)| {
             locations
                 .iter()
                 .map(|x| Position::new(*x, line_no))
-                .collect(),
-        );
-    }
+                .collect()
+        })
+        .collect();
     let universe = expand_universe(universe);
     //println!("{:?}", universe);
-    let max_distance = sum_all_positions(&universe);
-    Ok(max_distance)
+    sum_all_positions(&universe)
 }
 
 fn sum_all_positions(all_positions: &Vec<Position>) -> usize {
@@ -98,10 +96,7 @@ fn expand_universe_columns(universe: Universe) -> Vec<Position> {
             if max_col < pos.column {
                 max_col = pos.column;
             }
-            universe_by_col
-                .entry(pos.column)
-                .or_insert(Vec::new())
-                .push(pos);
+            universe_by_col.entry(pos.column).or_default().push(pos);
         }
     }
     let mut augment: usize = 0;
@@ -137,7 +132,7 @@ fn expand_universe_rows(universe: Universe) -> Universe {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
+    use crate::input::Input;
 
     #[test]
     fn test_simple_input() {
@@ -152,8 +147,9 @@ mod tests {
 .......#..
 #...#.....
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(82000210, result.unwrap());
     }
 
@@ -184,7 +180,7 @@ mod tests {
     }
 
     fn test_distance_single(a: &Position, b: &Position, expect: usize) {
-        assert_eq!(expect, a.distance(&b));
-        assert_eq!(a.distance(&b), b.distance(&a));
+        assert_eq!(expect, a.distance(b));
+        assert_eq!(a.distance(b), b.distance(a));
     }
 }
@@ -1,29 +1,70 @@
+use crate::answer::Answer;
+use crate::day_12_2;
+use crate::error::{AocError, ParseContext};
+use crate::input::Input;
 use crate::re_utils;
+use crate::solution::Solution;
 
 use anyhow::Result;
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let _galaxy_char = '#';
-    let mut sum: usize = 0;
-    for line in lines {
+// a row of springs, `?` when its condition is unknown, and the sizes of the
+// groups of damaged ones
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub springs: String,
+    pub groups: Vec<usize>,
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = 12;
+    type Parsed<'a> = Vec<Record>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_records(&input.lines())
+    }
+
+    fn part_one(records: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(count_arrangements(records).into())
+    }
+
+    fn part_two(records: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_12_2::count_unfolded_arrangements(records).into())
+    }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(count_arrangements(&parse_records(lines)?))
+}
+
+fn count_arrangements(records: &[Record]) -> usize {
+    records
+        .iter()
+        .map(|record| brute_force(&record.springs, &record.groups))
+        .sum()
+}
+
+// every record of the list, blank lines are skipped
+pub fn parse_records(lines: &[&str]) -> Result<Vec<Record>> {
+    let mut records = vec![];
+    for (i, line) in lines.iter().enumerate() {
         if line.is_empty() {
             continue;
         }
-        let (seq, groups) = get_components(&line);
-        //println!("Processing {}, {:?}", seq, groups);
-        sum += brute_force(&seq, &groups);
+        records.push(parse_record(line).at_line(i)?);
     }
-    Ok(sum)
+    Ok(records)
 }
 
-fn get_components(line: &str) -> (String, Vec<usize>) {
-    let mut split = line.split(" ");
-    let text = split.next().unwrap();
-    let groups = split.next().unwrap();
-    (
-        text.to_string(),
-        re_utils::parse_line_numbers(groups).expect("Invalid group numbers"),
-    )
+pub fn parse_record(line: &str) -> Result<Record> {
+    let (text, groups) = line.split_once(' ').ok_or_else(|| {
+        AocError::parse(1, "Expected springs and group sizes separated by a space")
+    })?;
+    Ok(Record {
+        springs: text.to_string(),
+        groups: re_utils::parse_line_numbers(groups)?,
+    })
 }
 
 fn brute_force(line: &str, groups: &Vec<usize>) -> usize {
@@ -50,7 +91,6 @@ fn brute_force(line: &str, groups: &Vec<usize>) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn test_simple_input() {
@@ -69,8 +109,9 @@ mod tests {
     }
 
     fn test_line(line: &str, expect: usize) {
-        let lines = utils::string_to_lines(line.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(line);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(expect, result.unwrap());
     }
 }
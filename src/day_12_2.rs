@@ -1,9 +1,8 @@
 use std::collections::HashMap;
 
-use crate::re_utils;
+use crate::day_12_1::{parse_records, Record};
 
 use anyhow::Result;
-use rayon;
 use rayon::prelude::*;
 
 /*
@@ -13,23 +12,21 @@ use rayon::prelude::*;
 */
 const UNFOLD_TIMES: usize = 5;
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let _galaxy_char = '#';
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(count_unfolded_arrangements(&parse_records(lines)?))
+}
+
+pub fn count_unfolded_arrangements(records: &[Record]) -> usize {
     let mut sum: usize = 0;
-    for line in lines {
-        if line.is_empty() {
-            continue;
-        }
-        //println!("Got line {}", line);
-        let line = unfold(&line);
-        let (seq, groups) = get_components(&line);
-        //println!("Processing {}, {:?}", seq, groups);
+    for record in records {
+        let record = unfold(record);
+        //println!("Processing {}, {:?}", record.springs, record.groups);
         let mut memo: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        let result = mutate_line(&seq, &groups, &mut memo);
-        //println!("Result for: {}, {:?}", line, result);
+        let result = mutate_line(&record.springs, &record.groups, &mut memo);
+        //println!("Result for: {:?}, {:?}", record, result);
         sum += result;
     }
-    Ok(sum)
+    sum
 }
 
 fn mutate_line(
@@ -100,45 +97,20 @@ fn count_chunk(chunk: &str) -> Vec<usize> {
         .collect()
 }
 
-fn unfold(line: &str) -> String {
-    let (seq, groups) = get_components(&line);
-    //println!("Got components seq {}, groups {:?}", seq, groups);
-    let mut result = String::with_capacity(line.len() * 5);
-    for _i in 0..UNFOLD_TIMES {
-        result.push_str(&seq);
-        result.push('?');
+// the springs repeated five times joined by `?`, and the groups five times
+fn unfold(record: &Record) -> Record {
+    Record {
+        springs: [record.springs.as_str(); UNFOLD_TIMES].join("?"),
+        groups: record.groups.repeat(UNFOLD_TIMES),
     }
-    let result = &result[..result.len() - 1];
-    let mut result = result.to_string();
-    result.push(' ');
-    let group_text = groups
-        .iter()
-        .map(|x| x.to_string() + ",")
-        .collect::<String>();
-
-    for _i in 0..UNFOLD_TIMES {
-        result.push_str(&group_text);
-    }
-    let result = result.trim_end_matches(",");
-    //println!("Return unfold {}", result);
-    result.to_string()
-}
-
-fn get_components(line: &str) -> (String, Vec<usize>) {
-    let mut split = line.split(" ");
-    let text = split.next().unwrap();
-    let groups = split.next().unwrap();
-    (
-        text.to_string(),
-        re_utils::parse_line_numbers(groups).expect("Invalid group numbers"),
-    )
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use crate::utils;
+    use crate::day_12_1::parse_record;
+    use crate::input::Input;
 
     //#[ignore]
     #[test]
@@ -160,28 +132,31 @@ mod tests {
     }
 
     fn test_line(line: &str, expect: usize) {
-        let lines = utils::string_to_lines(line.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(line);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(expect, result.unwrap());
     }
 
     #[test]
-    fn test_unfold() {
+    fn test_unfold() -> Result<()> {
+        let record = parse_record("???.### 1,1,3")?;
         assert_eq!(
-            "???.###????.###????.###????.###????.### 1,1,3,1,1,3,1,1,3,1,1,3,1,1,3",
-            unfold("???.### 1,1,3")
+            parse_record("???.###????.###????.###????.###????.### 1,1,3,1,1,3,1,1,3,1,1,3,1,1,3")?,
+            unfold(&record)
         );
+        Ok(())
     }
 
     #[test]
     fn test_strip_prefix() {
-        let a = vec![1, 2, 3];
+        let a = [1, 2, 3];
         let b = vec![1, 2, 3];
         let expect: Vec<usize> = vec![];
         if let Some(suffix) = a.strip_prefix(b.as_slice()) {
             assert_eq!(expect, suffix);
         } else {
-            assert!(false);
+            panic!("Expected prefix to be stripped");
         }
     }
 }
@@ -3,20 +3,18 @@ use std::cell;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt::Display;
 use std::ops::Index;
-use std::sync::atomic::AtomicUsize;
 
-use crate::col_utils;
+use crate::answer::Answer;
+use crate::cycle;
+use crate::input::Input;
 use crate::prelude::*;
-use crate::re_utils;
-use crate::utils::rotate_block;
-use anyhow::Result;
+use crate::solution::{DayPart, Solution};
+use anyhow::{Context, Result};
 use rayon::iter::Map;
 
-type Loc = (i64, i64);
-
 const TOTAL_CYCLES: usize = 1000000000;
-const MEMO_HIT: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Clone, PartialEq, Hash, Eq)]
 enum Direction {
@@ -42,6 +40,16 @@ impl Direction {
 
         delta.0 > 0 || delta.1 > 0
     }
+
+    // clockwise quarter turns that make this direction point north
+    fn turns_to_north(&self) -> isize {
+        match self {
+            Self::North => 0,
+            Self::West => 1,
+            Self::South => 2,
+            Self::East => -1,
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Hash, Eq)]
@@ -52,30 +60,41 @@ enum CellType {
 }
 
 #[derive(Clone, PartialEq, Hash, Eq)]
-struct Cell {
+pub struct Cell {
     cell_type: CellType,
 }
 
+impl TryFrom<char> for Cell {
+    type Error = String;
+
+    fn try_from(c: char) -> std::result::Result<Self, Self::Error> {
+        let cell_type = match c {
+            '.' => CellType::Empty,
+            'O' => CellType::Round,
+            '#' => CellType::Cube,
+            _ => return Err(format!("Unexpected char [{}]", c)),
+        };
+        Ok(Cell { cell_type })
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.char())
+    }
+}
+
 impl Cell {
     fn is_round(&self) -> bool {
-        match (self.cell_type) {
-            CellType::Round => true,
-            _ => false,
-        }
+        matches!(self.cell_type, CellType::Round)
     }
 
     fn is_cube(&self) -> bool {
-        match (self.cell_type) {
-            CellType::Cube => true,
-            _ => false,
-        }
+        matches!(self.cell_type, CellType::Cube)
     }
 
     fn is_empty(&self) -> bool {
-        match (self.cell_type) {
-            CellType::Empty => true,
-            _ => false,
-        }
+        matches!(self.cell_type, CellType::Empty)
     }
 
     fn char(&self) -> char {
@@ -87,180 +106,117 @@ impl Cell {
     }
 }
 
-pub fn process(lines: Vec<String>, day_part: usize) -> Result<usize> {
-    let mut sum: usize = 0;
-    let mut line_aggregator: Vec<String> = Vec::new();
-    for line in lines {
-        if line.is_empty() {
-            sum += process_block(&line_aggregator.as_slice(), day_part);
-            line_aggregator.clear();
-        } else {
-            line_aggregator.push(line);
-        }
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+    // every platform of the input, they are separated by blank lines
+    type Parsed<'a> = Vec<Grid<Cell>>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        input
+            .blocks()
+            .map(|block| {
+                Grid::from_chars(&block).with_context(|| {
+                    format!("In the platform at line {}", input.line_index(block[0]) + 1)
+                })
+            })
+            .collect()
     }
-    sum += process_block(&line_aggregator.as_slice(), day_part);
-    Ok(sum)
-}
-
-type CellMap = HashMap<Loc, Cell>;
 
-fn map_to_str(cell_map: &CellMap, max_loc: (usize, usize)) -> Vec<String> {
-    let mut result: Vec<String> = vec![];
-    for x in 0..max_loc.0 {
-        let mut text = String::new();
-        for y in 0..max_loc.1 {
-            let loc = (x as i64, y as i64);
-            let cell = cell_map.get(&loc).unwrap();
-            text.push(cell.char());
-        }
-        result.push(text);
+    fn part_one(grids: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(grids
+            .iter()
+            .map(|grid| process_grid(grid, DayPart::One))
+            .sum::<usize>()
+            .into())
     }
 
-    result
-}
-
-fn map_to_line(cell_map: &CellMap, max_loc: (usize, usize)) -> String {
-    let mut result = String::new();
-    for x in 0..max_loc.0 {
-        let mut text = String::new();
-        for y in 0..max_loc.1 {
-            let loc = (x as i64, y as i64);
-            let cell = cell_map.get(&loc).unwrap();
-            text.push(cell.char());
-        }
+    fn part_two(grids: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(grids
+            .iter()
+            .map(|grid| process_grid(grid, DayPart::Two))
+            .sum::<usize>()
+            .into())
     }
-    result
 }
 
-type MemoKey = (Direction, String);
-type MemoMap = HashMap<String, usize>;
-fn process_block(lines: &[String], day_part: usize) -> usize {
-    if lines.is_empty() {
-        return 0;
-    }
-    let mut cell_map: HashMap<Loc, Cell> = parse_map(lines);
-    let max_loc = (lines.len(), lines[0].len());
-    let mut memo: MemoMap = HashMap::new();
-
-    if day_part == 1 {
-        push_rocks(&cell_map, Direction::North, max_loc);
-        //let map_as_text = map_to_str(&cell_map, max_loc);
-        return calculate_north_load(&cell_map, max_loc);
+fn process_grid(grid: &Grid<Cell>, day_part: DayPart) -> usize {
+    if day_part == DayPart::One {
+        return calculate_north_load(grid);
     }
 
-    let mut i: usize = 0;
-    let mut is_cycle_found: bool = false;
-    loop {
-        if i >= TOTAL_CYCLES {
-            break;
-        }
-        let memo_key = map_to_line(&cell_map, max_loc);
-        if !is_cycle_found && memo.contains_key(&memo_key) {
-            let cycle_start = memo.get(&memo_key).unwrap();
-            println!(
-                "Found cycle at iteration [{}], cycle start [{}]",
-                i, cycle_start
-            );
-            let mod_cycle = TOTAL_CYCLES % cycle_start;
-            let new_i = TOTAL_CYCLES - mod_cycle;
-            i = new_i;
-            is_cycle_found = true;
-        }
-        let cycled_map = Some(&cell_map)
-            .map(|cm| push_rocks(&cm, Direction::North, max_loc))
-            .map(|cm| push_rocks(&cm, Direction::West, max_loc))
-            .map(|cm| push_rocks(&cm, Direction::South, max_loc))
-            .map(|cm| push_rocks(&cm, Direction::East, max_loc))
-            .unwrap();
-        let memo_key = map_to_line(&cycled_map, max_loc);
-        memo.insert(memo_key, i - 1);
-        i += 1;
-
-        cell_map = cycled_map;
-    }
+    // the rocks settle in a loop of states long before the last cycle
+    let cycle = cycle::hashed(grid.clone(), spin, TOTAL_CYCLES);
+    north_load(&cycle.state)
+}
 
-    calculate_north_load(&cell_map, max_loc)
+fn spin(cell_map: &Grid<Cell>) -> Grid<Cell> {
+    [
+        Direction::North,
+        Direction::West,
+        Direction::South,
+        Direction::East,
+    ]
+    .into_iter()
+    .fold(cell_map.clone(), |cm, direction| push_rocks(&cm, direction))
 }
 
-fn push_rocks(cell_map_source: &CellMap, direction: Direction, max_loc: (usize, usize)) -> CellMap {
-    let mut cell_map = cell_map_source.clone();
-    let delta = direction.delta();
-    let max_x = max_loc.0 as i64;
-    let max_y = max_loc.1 as i64;
-    for x in 0..max_loc.0 {
-        let real_x = if direction.is_start_backwards() {
-            max_loc.0 - x - 1
-        } else {
-            x
-        };
-        for y in 0..max_loc.1 {
-            let real_y = if direction.is_start_backwards() {
-                max_loc.1 - y - 1
-            } else {
-                y
-            };
-            let mut loc = (real_x as i64, real_y as i64);
-            let cell_in_loc = cell_map.get(&loc).unwrap().clone();
-            if cell_in_loc.is_round() {
-                loop {
-                    let loc_delta = (loc.0 + delta.0, loc.1 + delta.1);
-                    if loc_delta.0 < 0 || loc_delta.1 < 0 {
-                        break;
-                    }
-                    if loc_delta.0 >= max_x || loc_delta.1 >= max_y {
-                        break;
-                    }
-                    let cell_in_delta = cell_map.get(&loc_delta).unwrap().clone();
-                    if !cell_in_delta.is_empty() {
-                        break;
-                    }
-                    //swap
-                    cell_map.insert(loc_delta, cell_in_loc.clone());
-                    cell_map.insert(loc, cell_in_delta);
-                    loc = loc_delta;
-                }
+// turns the grid so `direction` points north, rolls the rocks and turns it
+// back
+fn push_rocks(cell_map_source: &Grid<Cell>, direction: Direction) -> Grid<Cell> {
+    let turns = direction.turns_to_north();
+    let mut cell_map = cell_map_source.rotate(turns);
+    tilt_north(&mut cell_map);
+    cell_map.rotate(-turns)
+}
+
+fn tilt_north(cell_map: &mut Grid<Cell>) {
+    let width = cell_map.width;
+    for x in 0..width {
+        // first row a rock rolling up this column would stop at
+        let mut free_y = 0;
+        for y in 0..cell_map.height {
+            let pos = y * width + x;
+            if cell_map.data[pos].is_cube() {
+                free_y = y + 1;
+            } else if cell_map.data[pos].is_round() {
+                cell_map.data.swap(pos, free_y * width + x);
+                free_y += 1;
             }
         }
     }
-    cell_map
 }
 
-fn parse_map(lines: &[String]) -> HashMap<Loc, Cell> {
-    let mut map: HashMap<Loc, Cell> = HashMap::new();
-    for (x, line) in lines.iter().enumerate() {
-        for (y, c) in line.chars().enumerate() {
-            let loc = (x as i64, y as i64);
-            let cell_type = match (c) {
-                '.' => CellType::Empty,
-                'O' => CellType::Round,
-                '#' => CellType::Cube,
-                _ => panic!("Could not parse invalid cell type"),
-            };
-            let cell = Cell { cell_type };
-
-            map.insert(loc, cell);
-        }
-    }
-    map
+// load of the rocks where they are, without rolling them
+fn north_load(cell_map: &Grid<Cell>) -> usize {
+    cell_map
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| cell.is_round())
+        .map(|(pos, _)| cell_map.height - pos / cell_map.width)
+        .sum()
 }
 
-fn calculate_north_load(cell_map: &CellMap, max_loc: (usize, usize)) -> usize {
+// load once the rocks roll north, counted without moving them
+fn calculate_north_load(cell_map: &Grid<Cell>) -> usize {
     let mut total: usize = 0;
-    for y in 0..max_loc.1 {
+    let height = cell_map.height;
+    for x in 0..cell_map.width {
         let mut count = 0;
         let mut offset = 0;
-        for x in 0..max_loc.0 {
-            let loc = (x as i64, y as i64);
-            let cell = cell_map.get(&loc).unwrap();
+        for y in 0..height {
+            let cell = &cell_map.data[y * cell_map.width + x];
             if cell.is_cube() {
-                total += calculate_value(count, max_loc.1 - offset);
-                offset = x + 1;
+                total += calculate_value(count, height - offset);
+                offset = y + 1;
                 count = 0;
             } else if cell.is_round() {
                 count += 1;
             }
         }
-        total += calculate_value(count, max_loc.1 - offset);
+        total += calculate_value(count, height - offset);
     }
     total
 }
@@ -277,7 +233,6 @@ fn calculate_value(o_count: usize, line_len: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn test_simple_input() {
@@ -296,15 +251,27 @@ O.#..O.#.#
     }
 
     fn test_line(line: &str, expect: usize) {
-        let lines = utils::string_to_lines(line.to_string());
-        let result = process(lines, 1);
-        assert_eq!(expect, result.unwrap());
+        let input = Input::from(line);
+        let result = Day14::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(expect), result.unwrap());
     }
 
     fn test_line_2(line: &str, expect: usize) {
-        let lines = utils::string_to_lines(line.to_string());
-        let result = process(lines, 2);
-        assert_eq!(expect, result.unwrap());
+        let input = Input::from(line);
+        let result = Day14::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(expect), result.unwrap());
+    }
+
+    #[test]
+    fn test_blocks() {
+        // every platform is loaded on its own and the loads add up
+        test_line("O.\n..\n\n\n.O\nO#\n\n", 2 + 4);
+        let input = Input::from("O.\n..\n\n.O\nOx");
+        let err = Day14::parse(&input).err().unwrap();
+        assert_eq!(
+            "In the platform at line 4: Parse error at line 2, column 2: Unexpected char [x]",
+            format!("{:#}", err)
+        );
     }
 
     #[test]
@@ -312,6 +279,7 @@ O.#..O.#.#
         assert_eq!(34, calculate_value(4, 10));
     }
 
+    #[test]
     fn test_simple_input_day_2() {
         let input = "O....#....
 O.OO#....#
@@ -1,28 +1,60 @@
-use core::panic;
 use std::collections::{HashMap, LinkedList, VecDeque};
 
 use anyhow::{bail, Result};
 use regex::Regex;
 
-pub fn process(lines: Vec<String>, day_part: usize) -> Result<usize> {
-    match day_part {
-        1 => Ok(process_d1(&lines)),
-        2 => Ok(process_d2(&lines)),
-        _ => panic!("Day part not implemented"),
+use crate::answer::Answer;
+use crate::error::{column_of, AocError};
+use crate::input::Input;
+use crate::solution::{DayPart, Solution};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+    type Parsed<'a> = Vec<SequenceStep<'a>>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_sequence(&input.lines())
+    }
+
+    fn part_one(steps: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process_d1(steps).into())
+    }
+
+    fn part_two(steps: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process_d2(steps).into())
+    }
+}
+
+// a step of the initialization sequence, as written for the hash and as the
+// operation it does on the boxes
+pub struct SequenceStep<'a> {
+    text: &'a str,
+    step: Step,
+}
+
+fn parse_sequence<'a>(lines: &[&'a str]) -> Result<Vec<SequenceStep<'a>>> {
+    let mut steps = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        for text in line.split(',').filter(|s| !s.is_empty()) {
+            let step = Step::of(text)
+                .map_err(|e| AocError::parse(column_of(line, text), e.to_string()).at_line(i))?;
+            steps.push(SequenceStep { text, step });
+        }
     }
+    Ok(steps)
 }
 
 // day 1
-fn process_d1(lines: &[String]) -> usize {
-    lines
-        .iter()
-        .map(|l| l.split(',').map(|step| hash(step)).sum::<usize>())
-        .sum()
+fn process_d1(steps: &[SequenceStep]) -> usize {
+    steps.iter().map(|s| hash(s.text)).sum()
 }
 // day 1
 
 // day 2
 
+#[derive(Clone)]
 struct Lens {
     label: String,
     focal_length: usize,
@@ -34,21 +66,16 @@ enum Step {
 }
 
 impl Step {
-    fn of(text: &str) -> Self {
-        let re = Regex::new(r"(\w+)([-=])(\d+)?").unwrap();
-        if let Some(groups) = re.captures(text) {
-            let label = groups.get(1).unwrap().as_str();
-            let sign = groups.get(2).unwrap().as_str();
-            return match sign {
-                "-" => Self::Dash(label.to_string()),
-                "=" => Self::Equals(Lens::new(
-                    label,
-                    groups.get(3).unwrap().as_str().parse().unwrap(),
-                )),
-                _ => panic!("invalid operation"),
-            };
-        }
-        panic!("Operation not matched [{}]", text)
+    fn of(text: &str) -> Result<Self> {
+        let re = Regex::new(r"^(\w+)(-|=(\d+))$")?;
+        let Some(groups) = re.captures(text) else {
+            bail!("Operation not matched [{}]", text);
+        };
+        let label = &groups[1];
+        Ok(match groups.get(3) {
+            None => Self::Dash(label.to_string()),
+            Some(focal_length) => Self::Equals(Lens::new(label, focal_length.as_str().parse()?)),
+        })
     }
 }
 
@@ -73,26 +100,20 @@ impl Lens {
     }
 }
 
-type MAP = HashMap<usize, VecDeque<Lens>>;
-fn process_d2(lines: &[String]) -> usize {
-    let step_it = lines
-        .iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| l.split(','))
-        .flatten();
-    let mut map: MAP = HashMap::with_capacity(256);
-    for s in step_it {
-        let step = Step::of(s);
-        match step {
-            Step::Dash(label) => remove_lens(&mut map, &label),
-            Step::Equals(lens) => add_lens(&mut map, lens),
+type Map = HashMap<usize, VecDeque<Lens>>;
+fn process_d2(steps: &[SequenceStep]) -> usize {
+    let mut map: Map = HashMap::with_capacity(256);
+    for s in steps {
+        match &s.step {
+            Step::Dash(label) => remove_lens(&mut map, label),
+            Step::Equals(lens) => add_lens(&mut map, lens.clone()),
         }
     }
     calculate_map_value(&map)
 }
 
-fn calculate_map_value(map: &MAP) -> usize {
-    let mut result = 0 as usize;
+fn calculate_map_value(map: &Map) -> usize {
+    let mut result = 0_usize;
     for k in map.keys() {
         let box_value = k + 1;
         let list = map.get(k).unwrap();
@@ -103,18 +124,16 @@ fn calculate_map_value(map: &MAP) -> usize {
     result
 }
 
-fn remove_lens(map: &mut MAP, label: &str) {
+fn remove_lens(map: &mut Map, label: &str) {
     let label_hash = hash(label);
     if let Some(list) = map.get_mut(&label_hash) {
         list.retain(|lens| !lens.is_same_label(label));
     }
 }
 
-fn add_lens(map: &mut MAP, lens: Lens) {
+fn add_lens(map: &mut Map, lens: Lens) {
     let label_hash = lens.label_hash();
-    if !map.contains_key(&label_hash) {
-        map.insert(label_hash, VecDeque::new());
-    }
+    map.entry(label_hash).or_default();
     let mut list = map.get_mut(&label_hash).unwrap();
     let mut is_contained = false;
     for e in list.iter_mut() {
@@ -154,7 +173,6 @@ fn calculate_current_value(current_value: usize, ascii_value: u8) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn test_hash_base_cases() {
@@ -178,16 +196,26 @@ mod tests {
     #[test]
     fn test_simple_input() {
         let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process(lines, 1);
-        assert_eq!(1320, result.unwrap());
+        let input = Input::from(input);
+        let result = Day15::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(1320), result.unwrap());
+    }
+
+    #[test]
+    fn test_invalid_step() {
+        let input = Input::from("rn=1,cm?");
+        let err = Day15::parse(&input).err().unwrap();
+        assert_eq!(
+            "Parse error at line 1, column 6: Operation not matched [cm?]",
+            err.to_string()
+        );
     }
 
     #[test]
     fn test_simple_input_day_2() {
         let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process(lines, 2);
-        assert_eq!(145, result.unwrap());
+        let input = Input::from(input);
+        let result = Day15::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(145), result.unwrap());
     }
 }
@@ -1,73 +1,157 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, Result};
+use bit_set::BitSet;
 use num::Integer;
-use rayon::{slice::Windows, spawn};
+use rayon::prelude::*;
 
-#[derive(Debug)]
-enum SpaceType {
-    Empty,
-    RightMirror,
-    LeftMirror,
-    VerticalSplitter,
-    HorizontalSplitter,
-}
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::prelude::{Coord, Grid};
+use crate::search;
+use crate::solution::{DayPart, Solution};
 
-#[derive(Debug, Eq, Hash, PartialEq, Clone)]
-enum Direction {
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+pub enum Direction {
     Up,
     Down,
     Left,
     Right,
 }
 
-struct ParseSpaceTypeError;
-
-impl SpaceType {
-    fn from_char(s: char) -> Result<Self> {
-        match s {
-            '.' => Ok(Self::Empty),
-            '/' => Ok(Self::RightMirror),
-            '\\' => Ok(Self::LeftMirror),
-            '-' => Ok(Self::HorizontalSplitter),
-            '|' => Ok(Self::VerticalSplitter),
-            _ => bail!("{} is not valid SpaceType", s),
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+impl Direction {
+    fn index(&self) -> usize {
+        match self {
+            Self::Up => 0,
+            Self::Down => 1,
+            Self::Left => 2,
+            Self::Right => 3,
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
         }
     }
 }
 
-type SpaceId = usize;
-#[derive(Debug)]
-struct Space {
-    id: SpaceId,
-    space_type: SpaceType,
-    up: Option<SpaceId>,
-    down: Option<SpaceId>,
-    left: Option<SpaceId>,
-    right: Option<SpaceId>,
+// What a tile does to a beam: for every incoming direction, the directions the
+// beam leaves in. A tile with no way out for a direction absorbs the beam
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tile {
+    outgoing: [Vec<Direction>; 4],
 }
 
-impl Default for Space {
+impl Tile {
+    pub fn new(rule: impl Fn(Direction) -> Vec<Direction>) -> Self {
+        Tile {
+            outgoing: DIRECTIONS.map(rule),
+        }
+    }
+
+    pub fn outgoing(&self, direction: &Direction) -> &[Direction] {
+        &self.outgoing[direction.index()]
+    }
+
+    // the beam goes on untouched
+    fn is_straight(&self, direction: &Direction) -> bool {
+        self.outgoing(direction) == [*direction]
+    }
+}
+
+// The tiles a map can be made of, by the char that stands for them
+#[derive(Clone, Debug)]
+pub struct Optics {
+    chars: Vec<char>,
+    tiles: Vec<Tile>,
+}
+
+impl Default for Optics {
+    // empty space, mirrors and splitters of the puzzle
     fn default() -> Self {
-        Self {
-            id: 0,
-            space_type: SpaceType::Empty,
-            up: None,
-            down: None,
-            left: None,
-            right: None,
+        Optics {
+            chars: vec![],
+            tiles: vec![],
         }
+        .with_tile('.', Tile::new(|d| vec![d]))
+        .with_tile(
+            '/',
+            Tile::new(|d| match d {
+                Direction::Up => vec![Direction::Right],
+                Direction::Down => vec![Direction::Left],
+                Direction::Left => vec![Direction::Down],
+                Direction::Right => vec![Direction::Up],
+            }),
+        )
+        .with_tile(
+            '\\',
+            Tile::new(|d| match d {
+                Direction::Up => vec![Direction::Left],
+                Direction::Down => vec![Direction::Right],
+                Direction::Left => vec![Direction::Up],
+                Direction::Right => vec![Direction::Down],
+            }),
+        )
+        .with_tile(
+            '|',
+            Tile::new(|d| match d {
+                Direction::Up | Direction::Down => vec![d],
+                _ => vec![Direction::Up, Direction::Down],
+            }),
+        )
+        .with_tile(
+            '-',
+            Tile::new(|d| match d {
+                Direction::Left | Direction::Right => vec![d],
+                _ => vec![Direction::Left, Direction::Right],
+            }),
+        )
     }
 }
 
-impl Space {
-    fn new_alone(space_type: SpaceType) -> Self {
-        Self {
-            space_type,
-            ..Default::default()
+impl Optics {
+    // adds a tile, or replaces the one already using that char
+    pub fn with_tile(mut self, c: char, tile: Tile) -> Self {
+        match self.index_of(c) {
+            Some(i) => self.tiles[i] = tile,
+            None => {
+                self.chars.push(c);
+                self.tiles.push(tile);
+            }
         }
+        self
     }
 
+    fn index_of(&self, c: char) -> Option<usize> {
+        self.chars.iter().position(|known| *known == c)
+    }
+}
+
+type SpaceId = usize;
+// a beam entering a space going in a direction
+type Beam = (SpaceId, Direction);
+#[derive(Debug, Default)]
+struct Space {
+    id: SpaceId,
+    tile: usize,
+    up: Option<SpaceId>,
+    down: Option<SpaceId>,
+    left: Option<SpaceId>,
+    right: Option<SpaceId>,
+}
+
+impl Space {
     fn from_id(id: SpaceId) -> Self {
         Self {
             id,
@@ -90,32 +174,53 @@ impl Space {
 
     fn next_from_direction(&self, direction: &Direction) -> Option<SpaceId> {
         match direction {
-            Direction::Right => self.right.clone(),
-            Direction::Left => self.left.clone(),
-            Direction::Up => self.up.clone(),
-            Direction::Down => self.down.clone(),
+            Direction::Right => self.right,
+            Direction::Left => self.left,
+            Direction::Up => self.up,
+            Direction::Down => self.down,
         }
     }
 }
 
-pub fn process(lines: Vec<String>, part: usize) -> Result<usize> {
-    match part {
-        1 => Ok(process_one(&lines, 0, &Direction::Right)),
-        2 => Ok(process_corners(&lines)),
-        _ => panic!("Day part not implemented"),
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+    type Parsed<'a> = SpaceMap;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_map(&input.lines())
+    }
+
+    fn part_one(space_map: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process_one(space_map, 0, &Direction::Right).into())
+    }
+
+    fn part_two(space_map: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process_corners(space_map).into())
+    }
+
+    // the beams of the part, part two shows the best edge entry
+    fn render(space_map: &Self::Parsed<'_>, part: DayPart) -> Result<Option<Grid<char>>> {
+        let start = match part {
+            DayPart::One => Some((0, Direction::Right)),
+            DayPart::Two => best_entry(space_map).map(|(beam, _)| beam),
+        };
+        Ok(start.map(|start| draw_energized(space_map, &energize(space_map, &start))))
     }
 }
 
 #[derive(Debug)]
-struct SpaceMap {
+pub struct SpaceMap {
     height: usize,
     width: usize,
     max_position: usize,
     map: Vec<Space>,
+    optics: Optics,
 }
 
 impl SpaceMap {
-    fn new(height: usize, width: usize) -> Self {
+    fn new(height: usize, width: usize, optics: Optics) -> Self {
         let max_position = (height * width);
         let mut map: Vec<Space> = Vec::with_capacity(max_position);
         for i in 0..=max_position {
@@ -126,13 +231,17 @@ impl SpaceMap {
             width,
             map,
             max_position,
+            optics,
         }
     }
 
-    fn add(&mut self, c: char, row: usize, col: usize) {
+    fn add(&mut self, tile: usize, row: usize, col: usize) {
         let pos = self.get_position(row, col);
-        let space_type = SpaceType::from_char(c);
-        self.map[pos].space_type = space_type.unwrap();
+        self.map[pos].tile = tile;
+    }
+
+    fn tile(&self, id: SpaceId) -> &Tile {
+        &self.optics.tiles[self.map[id].tile]
     }
 
     fn populate_neighbors(&mut self) {
@@ -176,29 +285,30 @@ fn get_coordinates(pos: usize, width: usize) -> (usize, usize) {
 }
 
 fn get_position(row: usize, col: usize, width: usize) -> usize {
-    let pos = (row * width) + col;
-    return pos;
+    (row * width) + col
 }
-fn parse_map(lines: &[String]) -> SpaceMap {
-    let map_height = lines.len();
-    let map_width = lines[0].len();
-    let mut space_map = SpaceMap::new(map_height, map_width);
-    for (row, line) in lines.iter().enumerate() {
-        for (col, c) in line.chars().enumerate() {
-            space_map.add(c, row, col);
-        }
-    }
-    space_map.populate_neighbors();
-    space_map
+fn parse_map(lines: &[&str]) -> Result<SpaceMap> {
+    parse_map_with(lines, Optics::default())
 }
 
-fn process_corners(lines: &[String]) -> usize {
-    if lines.is_empty() {
-        return 0;
+fn parse_map_with(lines: &[&str], optics: Optics) -> Result<SpaceMap> {
+    let grid: Grid<usize> = Grid::from_chars_with(lines, |c| {
+        optics
+            .index_of(c)
+            .ok_or_else(|| anyhow!("{} is not a known tile", c))
+    })?;
+    let mut space_map = SpaceMap::new(grid.height, grid.width, optics);
+    for (pos, tile) in grid.data.into_iter().enumerate() {
+        let (row, col) = get_coordinates(pos, grid.width);
+        space_map.add(tile, row, col);
     }
-    let space_map = parse_map(lines);
+    space_map.populate_neighbors();
+    Ok(space_map)
+}
 
-    let mut corners: Vec<(SpaceId, Direction)> = vec![];
+// every beam entering the map from its edges
+fn edge_entries(space_map: &SpaceMap) -> Vec<Beam> {
+    let mut corners: Vec<Beam> = vec![];
     let width = space_map.width;
     let height = space_map.height;
     for i in 0..space_map.max_position {
@@ -207,7 +317,7 @@ fn process_corners(lines: &[String]) -> usize {
             corners.push((i, Direction::Down));
         }
         // bottom row, up
-        if i > (height - 1) * width {
+        if i >= (height - 1) * width {
             corners.push((i, Direction::Up));
         }
         // left col, right
@@ -220,158 +330,184 @@ fn process_corners(lines: &[String]) -> usize {
         }
     }
     corners
-        .iter()
-        .map(|(pos, dir)| beam_walker(&space_map, *pos, dir))
-        .max()
+}
+
+fn process_corners(space_map: &SpaceMap) -> usize {
+    best_entry(space_map)
+        .map(|(_, energized)| energized)
         .unwrap_or(0)
 }
-fn process_one(lines: &[String], start_pos: SpaceId, start_direction: &Direction) -> usize {
-    if lines.is_empty() {
-        return 0;
-    }
-    let space_map = parse_map(lines);
-    //println!("Populated map [{:?}]", space_map);
-    beam_walker(&space_map, start_pos, start_direction)
+
+// the edge beam that energizes the most spaces, with how many it does
+fn best_entry(space_map: &SpaceMap) -> Option<(Beam, usize)> {
+    let graph = BeamGraph::new(space_map);
+    edge_entries(space_map)
+        .into_par_iter()
+        .map(|beam| (beam, graph.energized(&beam)))
+        .max_by_key(|(_, energized)| *energized)
 }
 
-fn beam_walker(space_map: &SpaceMap, start_pos: usize, start_direction: &Direction) -> usize {
-    let capacity = space_map.max_position;
-    let mut energized: HashSet<SpaceId> = HashSet::with_capacity(capacity);
-    let mut up_beam: HashSet<SpaceId> = HashSet::with_capacity(capacity);
-    let mut down_meam: HashSet<SpaceId> = HashSet::with_capacity(capacity);
-    let mut left_beam: HashSet<SpaceId> = HashSet::with_capacity(capacity);
-    let mut right_beam: HashSet<SpaceId> = HashSet::with_capacity(capacity);
-    let mut beam_map: HashMap<Direction, HashSet<SpaceId>> = HashMap::new();
-    beam_map.insert(Direction::Up, up_beam);
-    beam_map.insert(Direction::Down, down_meam);
-    beam_map.insert(Direction::Left, left_beam);
-    beam_map.insert(Direction::Right, right_beam);
-    let mut queue: VecDeque<(SpaceId, Direction)> = VecDeque::new();
-    queue.push_front((start_pos, start_direction.clone()));
-    while !queue.is_empty() {
-        let (id, direction) = queue.pop_front().unwrap();
-        energized.insert(id);
+// A straight run of a beam, from the space it enters to the mirror or
+// splitter that turns it, or to the edge of the map
+#[derive(Debug)]
+struct Segment {
+    spaces: Vec<SpaceId>,
+    exits: Vec<Beam>,
+}
+
+fn walk_segment(space_map: &SpaceMap, start: &Beam) -> Segment {
+    let (mut id, direction) = *start;
+    let mut spaces = vec![];
+    loop {
+        spaces.push(id);
         let space = &space_map.map[id];
-        match (&space.space_type, &direction) {
-            // to continue thru
-            (SpaceType::Empty, _)
-            | (SpaceType::HorizontalSplitter, Direction::Right)
-            | (SpaceType::HorizontalSplitter, Direction::Left)
-            | (SpaceType::VerticalSplitter, Direction::Up)
-            | (SpaceType::VerticalSplitter, Direction::Down) => {
-                let next = space.next_from_direction(&direction);
-                if let Some(n) = next {
-                    let mut set = beam_map.get_mut(&direction).unwrap();
-                    if !set.contains(&n) {
-                        set.insert(n);
-                        queue.push_front((n, direction));
-                    }
-                }
-            }
-            (SpaceType::VerticalSplitter, Direction::Right)
-            | (SpaceType::VerticalSplitter, Direction::Left) => {
-                if let Some(u) = space.up {
-                    let mut set = beam_map.get_mut(&Direction::Up).unwrap();
-                    if !set.contains(&u) {
-                        set.insert(u);
-                        queue.push_front((u, Direction::Up));
-                    }
-                }
-                if let Some(d) = space.down {
-                    let mut set = beam_map.get_mut(&Direction::Down).unwrap();
-                    if !set.contains(&d) {
-                        set.insert(d);
-                        queue.push_front((d, Direction::Down));
-                    }
-                }
-            }
-            (SpaceType::LeftMirror, Direction::Right)
-            | (SpaceType::RightMirror, Direction::Left) => {
-                if let Some(d) = space.down {
-                    let mut set = beam_map.get_mut(&Direction::Down).unwrap();
-                    if !set.contains(&d) {
-                        set.insert(d);
-                        queue.push_front((d, Direction::Down));
-                    }
-                }
-            }
-            (SpaceType::LeftMirror, Direction::Left)
-            | (SpaceType::RightMirror, Direction::Right) => {
-                if let Some(u) = space.up {
-                    let mut set = beam_map.get_mut(&Direction::Up).unwrap();
-                    if !set.contains(&u) {
-                        set.insert(u);
-                        queue.push_front((u, Direction::Up));
-                    }
+        let tile = space_map.tile(id);
+        if !tile.is_straight(&direction) {
+            let exits = tile
+                .outgoing(&direction)
+                .iter()
+                .filter_map(|out| space.next_from_direction(out).map(|next| (next, *out)))
+                .collect();
+            return Segment { spaces, exits };
+        }
+        match space.next_from_direction(&direction) {
+            Some(next) => id = next,
+            None => {
+                return Segment {
+                    spaces,
+                    exits: vec![],
                 }
             }
-            (SpaceType::LeftMirror, Direction::Down) | (SpaceType::RightMirror, Direction::Up) => {
-                if let Some(u) = space.right {
-                    let mut set = beam_map.get_mut(&Direction::Right).unwrap();
-                    if !set.contains(&u) {
-                        set.insert(u);
-                        queue.push_front((u, Direction::Right));
-                    }
+        }
+    }
+}
+
+// The segments a beam can follow, with loops of segments collapsed into
+// their strongly connected component and the spaces each component energizes
+// computed once, so any number of entry beams can be looked up
+struct BeamGraph {
+    segment_of: HashMap<Beam, usize>,
+    component_of: Vec<usize>,
+    energized: Vec<BitSet>,
+}
+
+impl BeamGraph {
+    fn new(space_map: &SpaceMap) -> Self {
+        // segments start at the edges and wherever a beam gets turned
+        let mut starts = edge_entries(space_map);
+        for (id, space) in space_map.map[..space_map.max_position].iter().enumerate() {
+            let tile = space_map.tile(id);
+            for direction in &DIRECTIONS {
+                if tile.is_straight(direction) {
+                    continue;
                 }
+                starts.extend(
+                    tile.outgoing(direction)
+                        .iter()
+                        .filter_map(|out| space.next_from_direction(out).map(|next| (next, *out))),
+                );
             }
-            (SpaceType::RightMirror, Direction::Down) | (SpaceType::LeftMirror, Direction::Up) => {
-                if let Some(u) = space.left {
-                    let mut set = beam_map.get_mut(&Direction::Left).unwrap();
-                    if !set.contains(&u) {
-                        set.insert(u);
-                        queue.push_front((u, Direction::Left));
-                    }
-                }
+        }
+        let mut segment_of: HashMap<Beam, usize> = HashMap::with_capacity(starts.len());
+        starts.retain(|beam| {
+            let index = segment_of.len();
+            *segment_of.entry(*beam).or_insert(index) == index
+        });
+
+        let segments: Vec<Segment> = starts
+            .par_iter()
+            .map(|beam| walk_segment(space_map, beam))
+            .collect();
+        let edges: Vec<Vec<usize>> = segments
+            .iter()
+            .map(|segment| segment.exits.iter().map(|exit| segment_of[exit]).collect())
+            .collect();
+
+        // components reach only the ones before them, so those are done
+        let components = search::strongly_connected_components(&edges);
+        let mut component_of = vec![0; segments.len()];
+        let mut energized: Vec<BitSet> = Vec::with_capacity(components.len());
+        for (c, component) in components.iter().enumerate() {
+            let mut spaces = BitSet::with_capacity(space_map.max_position);
+            for segment in component {
+                component_of[*segment] = c;
             }
-            (SpaceType::HorizontalSplitter, Direction::Down)
-            | (SpaceType::HorizontalSplitter, Direction::Up) => {
-                if let Some(u) = space.left {
-                    let mut set = beam_map.get_mut(&Direction::Left).unwrap();
-                    if !set.contains(&u) {
-                        set.insert(u);
-                        queue.push_front((u, Direction::Left));
-                    }
-                }
-                if let Some(u) = space.right {
-                    let mut set = beam_map.get_mut(&Direction::Right).unwrap();
-                    if !set.contains(&u) {
-                        set.insert(u);
-                        queue.push_front((u, Direction::Right));
+            for segment in component {
+                spaces.extend(segments[*segment].spaces.iter().copied());
+                for next in &edges[*segment] {
+                    if component_of[*next] != c {
+                        spaces.union_with(&energized[component_of[*next]]);
                     }
                 }
             }
+            energized.push(spaces);
+        }
 
-            (_, _) => todo!("{:?},{:?} not implemented", &space.space_type, &direction),
+        BeamGraph {
+            segment_of,
+            component_of,
+            energized,
         }
     }
-    //print_energized(&energized, space_map.height, space_map.width);
-    energized.len()
+
+    // number of spaces a beam entering an edge energizes
+    fn energized(&self, beam: &Beam) -> usize {
+        self.segment_of
+            .get(beam)
+            .map(|segment| self.energized[self.component_of[*segment]].len())
+            .unwrap_or(0)
+    }
+}
+
+fn process_one(space_map: &SpaceMap, start_pos: SpaceId, start_direction: &Direction) -> usize {
+    //println!("Populated map [{:?}]", space_map);
+    beam_walker(space_map, start_pos, start_direction)
 }
 
-fn print_energized(energized: &HashSet<SpaceId>, height: usize, width: usize) {
-    let mut line = String::with_capacity(width);
-    for row in 0..height {
-        for col in 0..width {
-            let id: usize = (row * width) + col;
-            if energized.contains(&id) {
-                line.push_str("#");
-            } else {
-                line.push_str(".");
+fn beam_walker(space_map: &SpaceMap, start_pos: usize, start_direction: &Direction) -> usize {
+    energize(space_map, &(start_pos, *start_direction)).len()
+}
+
+// spaces a beam goes through, following every split
+fn energize(space_map: &SpaceMap, start: &Beam) -> BitSet {
+    let mut energized = BitSet::with_capacity(space_map.max_position);
+    // beams already followed, by space and direction
+    let mut seen = BitSet::with_capacity(space_map.max_position * 4);
+    let mut queue: Vec<Beam> = vec![*start];
+    seen.insert(start.0 * 4 + start.1.index());
+    while let Some((id, direction)) = queue.pop() {
+        energized.insert(id);
+        let space = &space_map.map[id];
+        for out in space_map.tile(id).outgoing(&direction) {
+            if let Some(next) = space.next_from_direction(out) {
+                if seen.insert(next * 4 + out.index()) {
+                    queue.push((next, *out));
+                }
             }
         }
-        //println!("{}", line);
-        line.clear();
     }
+    energized
+}
+
+// the map with every energized empty space as `#`, mirrors and splitters
+// are kept so the path of the beams can be followed
+fn draw_energized(space_map: &SpaceMap, energized: &BitSet) -> Grid<char> {
+    let mut grid = Grid::init(space_map.width, space_map.height, '.');
+    for (id, space) in space_map.map[..space_map.max_position].iter().enumerate() {
+        grid.data[id] = space_map.optics.chars[space.tile];
+    }
+    let beams: Vec<Coord> = energized
+        .iter()
+        .filter(|id| grid.data[*id] == '.')
+        .map(|id| Coord::from_pos(id, space_map.width))
+        .collect();
+    grid.overlay().highlight(beams, '#').to_chars()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
-
-    #[test]
-    fn test_simple_input() {
-        let input = r".|...\....
+    const EXAMPLE: &str = r".|...\....
 |.-.\.....
 .....|-...
 ........|.
@@ -381,25 +517,173 @@ mod tests {
 .-.-/..|..
 .|....-|.\
 ..//.|....";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process(lines, 1);
-        assert_eq!(46, result.unwrap());
+
+    #[test]
+    fn test_simple_input() {
+        let input = EXAMPLE;
+        let input = Input::from(input);
+        let result = Day16::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(46), result.unwrap());
     }
 
     #[test]
     fn test_simple_input_two() {
-        let input = r".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process(lines, 2);
-        assert_eq!(51, result.unwrap());
+        let input = EXAMPLE;
+        let input = Input::from(input);
+        let result = Day16::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(51), result.unwrap());
+    }
+
+    #[test]
+    fn test_render() -> Result<()> {
+        let space_map = parse_map(&[".\\.", "|.-"])?;
+        let energized: BitSet = [0, 1, 5].into_iter().collect();
+        assert_eq!(
+            "#\\.\n|.-",
+            draw_energized(&space_map, &energized).to_string()
+        );
+
+        let space_map = parse_map(&EXAMPLE.lines().collect::<Vec<_>>())?;
+        let expected = r"#|###\....
+|#-.\#....
+.#...|-###
+.#...##.|.
+.#...##...
+.#...##..\
+.#../#\\..
+#-#-/##|..
+.|####-|.\
+.#//.|.#..";
+        let drawing = Day16::render(&space_map, DayPart::One)?.unwrap();
+        assert_eq!(expected, drawing.to_string());
+        // the best entry comes down the fourth column
+        let expected = r".|###\....
+|#-#\#....
+.#.#.|-###
+.#.#.##.|.
+.#.#.##...
+.#.#.##..\
+.#.#/#\\..
+#-#-/##|..
+.|####-|.\
+.#//.|.#..";
+        let drawing = Day16::render(&space_map, DayPart::Two)?.unwrap();
+        assert_eq!(expected, drawing.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_beam_graph() -> Result<()> {
+        let lines: Vec<&str> = EXAMPLE.lines().collect();
+        let space_map = parse_map(&lines)?;
+        let graph = BeamGraph::new(&space_map);
+        let entries = edge_entries(&space_map);
+        assert_eq!(40, entries.len());
+        for (pos, dir) in &entries {
+            assert_eq!(
+                beam_walker(&space_map, *pos, dir),
+                graph.energized(&(*pos, *dir)),
+                "entering {} going {:?}",
+                pos,
+                dir
+            );
+        }
+        Ok(())
+    }
+
+    fn tile(optics: &Optics, c: char) -> &Tile {
+        &optics.tiles[optics.index_of(c).unwrap()]
+    }
+
+    #[test]
+    fn test_mirrors() {
+        let optics = Optics::default();
+        let slash = tile(&optics, '/');
+        assert_eq!([Direction::Up], slash.outgoing(&Direction::Right));
+        assert_eq!([Direction::Left], slash.outgoing(&Direction::Down));
+        let backslash = tile(&optics, '\\');
+        assert_eq!([Direction::Down], backslash.outgoing(&Direction::Right));
+        assert_eq!([Direction::Left], backslash.outgoing(&Direction::Up));
+        assert!(DIRECTIONS.iter().all(|d| !slash.is_straight(d)));
+        assert!(DIRECTIONS.iter().all(|d| tile(&optics, '.').is_straight(d)));
+    }
+
+    #[test]
+    fn test_splitters() {
+        let optics = Optics::default();
+        let pipe = tile(&optics, '|');
+        assert!(pipe.is_straight(&Direction::Up) && pipe.is_straight(&Direction::Down));
+        assert_eq!(
+            [Direction::Up, Direction::Down],
+            pipe.outgoing(&Direction::Left)
+        );
+        let dash = tile(&optics, '-');
+        assert!(dash.is_straight(&Direction::Right));
+        assert_eq!(
+            [Direction::Left, Direction::Right],
+            dash.outgoing(&Direction::Down)
+        );
+    }
+
+    // runs the beam and the segment graph and checks they agree
+    fn energized_with(lines: &[&str], optics: Optics, beam: Beam) -> usize {
+        let space_map = parse_map_with(lines, optics).unwrap();
+        let walked = beam_walker(&space_map, beam.0, &beam.1);
+        assert_eq!(walked, BeamGraph::new(&space_map).energized(&beam));
+        walked
+    }
+
+    #[test]
+    fn test_absorber() {
+        let optics = Optics::default().with_tile('#', Tile::new(|_| vec![]));
+        assert_eq!(
+            3,
+            energized_with(&["..#.."], optics.clone(), (0, Direction::Right))
+        );
+        assert_eq!(3, energized_with(&["..#.."], optics, (4, Direction::Left)));
+    }
+
+    #[test]
+    fn test_one_way_gate() {
+        let gate = Tile::new(|d| match d {
+            Direction::Right => vec![d],
+            _ => vec![],
+        });
+        let optics = Optics::default().with_tile('>', gate);
+        assert_eq!(
+            3,
+            energized_with(&[".>."], optics.clone(), (0, Direction::Right))
+        );
+        assert_eq!(
+            2,
+            energized_with(&[".>."], optics.clone(), (2, Direction::Left))
+        );
+        // coming from below the gate stops the beam
+        assert_eq!(
+            2,
+            energized_with(&[".>.", "..."], optics, (4, Direction::Up))
+        );
+    }
+
+    #[test]
+    fn test_four_way_splitter() {
+        let optics = Optics::default().with_tile(
+            '+',
+            Tile::new(|d| {
+                DIRECTIONS
+                    .into_iter()
+                    .filter(|out| *out != d.opposite())
+                    .collect()
+            }),
+        );
+        let lines = ["...", ".+.", "..."];
+        assert_eq!(
+            5,
+            energized_with(&lines, optics.clone(), (3, Direction::Right))
+        );
+        assert!(parse_map(&lines).is_err());
+        // replacing a standard tile changes every map using it
+        let optics = optics.with_tile('.', Tile::new(|_| vec![]));
+        assert_eq!(1, energized_with(&lines, optics, (3, Direction::Right)));
     }
 }
@@ -1,45 +1,50 @@
-use std::collections::BinaryHeap;
-use std::collections::HashSet;
-use std::collections::VecDeque;
 use std::ops::RangeInclusive;
-use std::usize;
 
-use anyhow::{bail, Result};
-use num::iter::Range;
-use num::One;
+use anyhow::{anyhow, bail, Result};
 
-pub fn process(lines: &[String], day_part: usize) -> Result<usize> {
-    match day_part {
-        1 => Ok(process_with_range(lines, 0..=3)),
-        2 => Ok(process_with_range(lines, 4..=10)),
-        _ => todo!(),
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::prelude::Grid;
+use crate::search::BucketQueue;
+use crate::solution::{DayPart, Solution};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: usize = 17;
+    // the heat lost in every block
+    type Parsed<'a> = Grid<u8>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        Grid::from_chars_with(&input.lines(), |c| match c.to_digit(10) {
+            Some(heat) => Ok(heat as u8),
+            None => bail!("Expected a digit, got [{}]", c),
+        })
+    }
+
+    fn part_one(grid: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process_with_range(grid, 0..=3)?.into())
+    }
+
+    fn part_two(grid: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process_with_range(grid, 4..=10)?.into())
     }
 }
 
 type HeatMap = Vec<u8>;
-type HeatPath = Vec<usize>;
 
-fn process_with_range(lines: &[String], directional_range: RangeInclusive<u8>) -> usize {
-    if lines.is_empty() {
-        return 0;
-    }
-    let height = lines.len();
-    let width = lines[0].len();
-    let mut map: HeatMap = Vec::with_capacity(height * width);
-    for line in lines {
-        for c in line.chars() {
-            map.push(c.to_digit(10).unwrap() as u8);
-        }
-    }
+// the most heat a single block can lose
+const MAX_HEAT: usize = 9;
+
+fn process_with_range(grid: &Grid<u8>, directional_range: RangeInclusive<u8>) -> Result<usize> {
+    let (height, width) = (grid.height, grid.width);
+    let map: &HeatMap = &grid.data;
     let end_pos = get_pos(width - 1, height - 1, width);
-    if let Some(map_path) = find_shortest_path(&map, height, width, 0, end_pos, directional_range) {
-        println!("Result: {:?}", map_path);
-        return map_path.heat_loss;
-    }
-    0
+    find_shortest_path(map, height, width, 0, end_pos, directional_range)
+        .ok_or_else(|| anyhow!("No path to the bottom-right corner"))
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 enum Direction {
     Up,
     Down,
@@ -57,6 +62,24 @@ impl Direction {
         ]
     }
 
+    fn index(&self) -> usize {
+        match self {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        }
+    }
+
+    fn from_index(index: usize) -> Self {
+        match index {
+            0 => Direction::Up,
+            1 => Direction::Down,
+            2 => Direction::Left,
+            _ => Direction::Right,
+        }
+    }
+
     fn is_oposite(&self, dir: &Direction) -> bool {
         match (&self, dir) {
             (Direction::Right, Direction::Left)
@@ -68,55 +91,33 @@ impl Direction {
     }
 }
 
-#[derive(Eq, PartialEq, Debug)]
-struct MapPath {
-    //path: HeatPath,
-    position: usize,
-    heat_loss: usize,
-    same_direction_len: u8,
-    direction: Direction,
-}
-impl Ord for MapPath {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.heat_loss.cmp(&self.heat_loss)
-    }
-}
-impl PartialOrd for MapPath {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+// A crucible state is where it is, the way it goes and how many blocks in a
+// row it went that way, from 1 to the longest run. Every state gets a slot in
+// dense arrays instead of living in a hash set
+struct StateIndex {
+    max_run: usize,
 }
 
-impl MapPath {
-    fn new(
-        position: usize,
-        same_direction_len: u8,
-        direction: Direction,
-        heat_loss: usize,
-    ) -> Self {
-        Self {
-            position,
-            heat_loss,
-            same_direction_len,
-            direction,
-        }
+impl StateIndex {
+    fn len(&self, positions: usize) -> usize {
+        positions * 4 * self.max_run
     }
 
-    fn move_one(&self, new_pos: usize, new_dir: &Direction, pos_heat_loss: u8) -> Self {
-        let direction_len = if self.direction == *new_dir {
-            self.same_direction_len + 1
-        } else {
-            1
-        };
+    fn encode(&self, pos: usize, dir: &Direction, run: usize) -> usize {
+        (pos * 4 + dir.index()) * self.max_run + run - 1
+    }
 
-        Self::new(
-            new_pos,
-            direction_len,
-            new_dir.clone(),
-            self.heat_loss + pos_heat_loss as usize,
-        )
+    fn decode(&self, state: usize) -> (usize, Direction, usize) {
+        let run = state % self.max_run + 1;
+        let dir = Direction::from_index((state / self.max_run) % 4);
+        (state / (4 * self.max_run), dir, run)
     }
 }
+
+// least heat lost going from start to end, moving at least `move_range.start()`
+// and at most `move_range.end()` blocks in a row before turning or stopping.
+// Dijkstra over every state, the heat of a block is at most 9 so a bucket
+// queue orders them
 fn find_shortest_path(
     map: &HeatMap,
     height: usize,
@@ -124,74 +125,63 @@ fn find_shortest_path(
     start_pos: usize,
     end_pos: usize,
     move_range: RangeInclusive<u8>,
-) -> Option<MapPath> {
-    let max_offset = *move_range.end() as usize * 9;
-    let mut path_queue: BinaryHeap<MapPath> = BinaryHeap::new();
-    let mut dist: Vec<usize> = (0..map.len()).map(|_| usize::MAX - max_offset).collect();
-    let mut visited = HashSet::new();
-    let mut path_queue: BinaryHeap<MapPath> = BinaryHeap::new();
+) -> Option<usize> {
+    let min_run = *move_range.start() as usize;
+    let max_run = *move_range.end() as usize;
+    if start_pos == end_pos {
+        return Some(0);
+    }
+    if max_run == 0 {
+        return None;
+    }
+    let states = StateIndex { max_run };
+    let mut dist: Vec<usize> = vec![usize::MAX; states.len(map.len())];
+    let mut queue: BucketQueue<usize> = BucketQueue::new(MAX_HEAT);
     let all_dirs = Direction::all();
-    dist[start_pos] = 0;
+
+    // the crucible can leave the start in any direction
     for dir in &all_dirs {
-        if is_dir_possbile(start_pos, width, height, &dir) {
-            let starting = MapPath::new(start_pos, 1, dir.clone(), 0);
-            path_queue.push(starting);
+        if is_dir_possbile(start_pos, width, height, dir) {
+            let new_pos = get_move_pos(start_pos, width, dir);
+            let state = states.encode(new_pos, dir, 1);
+            dist[state] = map[new_pos] as usize;
+            queue.push(dist[state], state);
         }
     }
 
-    let mut result_path: Option<MapPath> = None;
-    let mut iter_count: usize = 0;
-    while let Some(path) = path_queue.pop() {
-        let last_pos = path.position;
-        if path.same_direction_len > *move_range.end() {
+    while let Some((heat_loss, state)) = queue.pop() {
+        if heat_loss > dist[state] {
+            // already reached with less heat lost
             continue;
         }
-        let current_in_range = move_range.contains(&path.same_direction_len);
-        if path.heat_loss < dist[last_pos] {
-            dist[last_pos] = path.heat_loss;
+        let (pos, direction, run) = states.decode(state);
+        if pos == end_pos && run >= min_run {
+            return Some(heat_loss);
         }
-        iter_count += 1;
-        if iter_count % 1000 == 0 {
-            println!(
-                "Iteration [{}] queue len [{}], current heat_loss [{}]",
-                iter_count,
-                path_queue.len(),
-                path.heat_loss
-            );
-        }
-
-        if current_in_range && last_pos == end_pos {
-            if let Some(prev) = &result_path {
-                if path.heat_loss < prev.heat_loss {
-                    result_path = Some(path)
-                }
-            } else {
-                result_path = Some(path)
-            };
-            continue;
-        }
-
         for dir in &all_dirs {
-            if path.direction.is_oposite(dir) {
+            if direction.is_oposite(dir) || !is_dir_possbile(pos, width, height, dir) {
                 continue;
             }
-            if !is_dir_possbile(last_pos, width, height, dir) {
+            let new_run = if *dir == direction {
+                run + 1
+            } else if run >= min_run {
+                1
+            } else {
                 continue;
-            }
-            let new_pos = get_move_pos(last_pos, width, dir);
-            let new_path = path.move_one(new_pos, dir, map[new_pos]);
-            let is_in_range = move_range.contains(&new_path.same_direction_len);
-            if *dir != path.direction && !current_in_range {
+            };
+            if new_run > max_run {
                 continue;
             }
-            if new_path.heat_loss < dist[new_pos] + max_offset {
-                if visited.insert((new_pos, dir, new_path.same_direction_len)) {
-                    path_queue.push(new_path);
-                }
+            let new_pos = get_move_pos(pos, width, dir);
+            let new_state = states.encode(new_pos, dir, new_run);
+            let new_heat_loss = heat_loss + map[new_pos] as usize;
+            if new_heat_loss < dist[new_state] {
+                dist[new_state] = new_heat_loss;
+                queue.push(new_heat_loss, new_state);
             }
         }
     }
-    result_path
+    None
 }
 
 fn get_move_pos(pos: usize, width: usize, dir: &Direction) -> usize {
@@ -242,7 +232,6 @@ fn get_pos(x: usize, y: usize, width: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn test_simple_input() {
@@ -259,11 +248,11 @@ mod tests {
 1224686865563
 2546548887735
 4322674655533";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process(&lines, 1);
-        assert_eq!(102, result.unwrap());
-        let result = process(&lines, 2);
-        assert_eq!(94, result.unwrap());
+        let input = Input::from(input);
+        let result = Day17::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(102), result.unwrap());
+        let result = Day17::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(94), result.unwrap());
     }
     #[test]
     fn test_simple_input_99() {
@@ -272,9 +261,9 @@ mod tests {
 99999
 99999
 99999";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process(&lines, 1);
-        assert_eq!(72, result.unwrap());
+        let input = Input::from(input);
+        let result = Day17::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(72), result.unwrap());
     }
     #[test]
     fn test_simple_input_day2_2() {
@@ -283,8 +272,55 @@ mod tests {
 999999999991
 999999999991
 999999999991";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process(&lines, 2);
-        assert_eq!(71, result.unwrap());
+        let input = Input::from(input);
+        let result = Day17::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(71), result.unwrap());
+    }
+
+    #[test]
+    fn test_no_path() {
+        // runs of four blocks never fit in a three block row
+        let input = Input::from("111");
+        let err = Day17::solve(&input, DayPart::Two).unwrap_err();
+        assert_eq!("No path to the bottom-right corner", err.to_string());
+    }
+
+    #[test]
+    fn test_invalid_block() {
+        let input = Input::from("241\n3x1");
+        let err = Day17::parse(&input).err().unwrap();
+        assert_eq!(
+            "Parse error at line 2, column 2: Expected a digit, got [x]",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_state_index() {
+        let states = StateIndex { max_run: 10 };
+        let mut seen = vec![false; states.len(6)];
+        for pos in 0..6 {
+            for dir in Direction::all() {
+                for run in 1..=10 {
+                    let state = states.encode(pos, &dir, run);
+                    assert!(!seen[state]);
+                    seen[state] = true;
+                    assert_eq!((pos, dir, run), states.decode(state));
+                }
+            }
+        }
+        assert!(seen.into_iter().all(|s| s));
+    }
+
+    #[test]
+    fn test_runs_at_their_limits() {
+        // three blocks is the longest run, so the first row ends right on time
+        let input = Input::from("1111\n9991\n9991\n9991");
+        let result = Day17::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(6), result.unwrap());
+        // four blocks is the shortest run, before turning and before stopping
+        let input = Input::from("11111\n99991\n99991\n99991\n99991");
+        let result = Day17::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(8), result.unwrap());
     }
 }
@@ -1,176 +1,212 @@
-use core::panic;
-use std::usize;
-
+use crate::answer::Answer;
+use crate::error::{column_of, AocError, ParseContext};
+use crate::input::Input;
+use crate::polygon;
 use crate::prelude::*;
-use anyhow::{bail, Result};
-
-#[derive(Clone, Debug, PartialEq)]
-enum Block {
-    Empty,
-    Wall,
-    Hole,
-    EdgeReachable,
+use crate::solution::{DayPart, Solution};
+use anyhow::Result;
+
+pub type Move = (Direction, usize);
+
+// the dig plan, the colour codes are only decoded by part two so part one
+// does not need them
+#[derive(Debug, Default)]
+pub struct DigPlan<'a> {
+    pub lines: Vec<&'a str>,
+    pub moves: Vec<Move>,
 }
 
-impl ToString for Block {
-    fn to_string(&self) -> String {
-        //format!("{:?}", self)
-        match self {
-            Block::Wall => "W",
-            Block::Hole => "H",
-            Block::Empty => ".",
-            Block::EdgeReachable => "+",
-        }
-        .to_string()
+impl DigPlan<'_> {
+    // the moves hidden in the colour codes
+    pub fn colour_moves(&self) -> Result<Vec<Move>> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| get_colour_components(line).at_line(i))
+            .collect()
     }
 }
 
-pub fn process(lines: &[String], day_part: usize) -> Result<usize> {
-    match day_part {
-        1 => process_one(lines),
-        _ => panic!(),
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+    type Parsed<'a> = DigPlan<'a>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        let lines = input.lines();
+        let moves = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| get_line_components(line).at_line(i))
+            .collect::<Result<Vec<Move>>>()?;
+        Ok(DigPlan { lines, moves })
     }
-}
 
-pub fn process_one(lines: &[String]) -> Result<usize> {
-    let end_coord = get_bounds(lines);
-    //println!("Starting grid with end coord {:?}", end_coord);
-    let mut grid: Grid<Block> =
-        Grid::init((end_coord.x * 2) + 1, (end_coord.y * 2) + 1, Block::Empty);
-    let mut position = end_coord;
-    grid.set(&position, Block::Wall);
-    for line in lines {
-        let (dir, len) = get_line_components(line).unwrap();
-        //println!("Parsed components {:?} {:?} from line {}", dir, len, line);
-        let delta = dir.coord_delta();
-        for m in 0..len {
-            //println!("Position {:?}, moving delta {:?}", position, delta);
-            position = position.plus_delta(delta).unwrap();
-            grid.set(&position, Block::Wall);
-        }
+    fn part_one(plan: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process(&plan.moves).into())
     }
 
-    let edge_reachable = grid.get_edge_reachable_pos_set(&Block::Wall);
-    //println!("Edge reachable [{:?}]", edge_reachable);
-    for ele in edge_reachable {
-        grid.data[ele] = Block::EdgeReachable;
+    fn part_two(plan: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process(&plan.colour_moves()?).into())
     }
-    return Ok(grid.count_eq(&Block::Empty) + grid.count_eq(&Block::Wall));
-
-    //grid.print();
-
-    //grid = mark_inner(&grid, &Block::Wall, &Block::Hole);
-    //println!("\nafter 0 rotation + marking");
-    //grid.print();
-    //for i in 1..4{
-    //    grid = grid.rotate();
-    //    grid = mark_inner(&grid, &Block::Wall, &Block::Hole);
-    //    println!("\nafter {i} rotation + marking");
-    //grid.print();
-    //}
-    //Ok(grid.count_ne(&Block::Empty))
-}
 
-// walks from west to east ,north to south,
-// the first orruccence of a C will open a parenthesis like logic
-// and it starts marking M until another occurrence of C happens
-pub fn mark_inner<E: ToString + Clone + PartialEq>(grid: &Grid<E>, c: &E, m: &E) -> Grid<E> {
-    let mut new_grid: Grid<E> = grid.clone();
-    for y in 1..grid.height - 1 {
-        let mut is_open = false;
-        let mut last_open_pos = 0;
-        for x in 0..grid.width {
-            let pos = (grid.width * y) + x;
-            let elem = &new_grid.data[pos];
-            if c == elem {
-                is_open = !is_open;
-                if is_open {
-                    last_open_pos = pos;
-                } else {
-                    // mark from last open to pos -1
-                    if last_open_pos == pos - 1 {
-                        continue;
-                    }
-                    for i in last_open_pos + 1..pos {
-                        new_grid.data[i] = m.clone();
-                    }
-                }
-            }
-        }
+    // the trench and the lagoon it encloses
+    fn render(plan: &Self::Parsed<'_>, part: DayPart) -> Result<Option<Grid<char>>> {
+        Ok(match part {
+            DayPart::One => draw_trench(&plan.moves),
+            DayPart::Two => draw_trench(&plan.colour_moves()?),
+        })
     }
-    new_grid
 }
 
-fn get_bounds(lines: &[String]) -> Coord {
-    let start_pos = usize::MAX / 2;
-    let mut pos = Coord::from(start_pos, start_pos);
-    let mut min_left = start_pos;
-    let mut max_right = start_pos;
-    let mut max_up = start_pos;
-    let mut min_down = start_pos;
-
-    for line in lines {
-        let (dir, len) = get_line_components(line).unwrap();
-        let (d_x, d_y) = dir.coord_delta();
-        let (d_x, d_y) = (d_x * len as i32, d_y * len as i32);
-
-        pos = pos.plus_delta((d_x, d_y)).unwrap();
-        if pos.x < min_left {
-            min_left = pos.x;
-        }
-        if pos.x > max_right {
-            max_right = pos.x;
-        }
-
-        if pos.y < min_down {
-            min_down = pos.y;
+// biggest drawing made, the part two trench is far too big to draw
+const MAX_DRAWING_CELLS: i64 = 4_000_000;
+
+// the trench dug as `#` and the lagoon inside it as `~`, with an empty border
+// around so the outside can be told apart from the lagoon, None when the
+// trench is too big to draw
+pub fn draw_trench(moves: &[Move]) -> Option<Grid<char>> {
+    let vertices = polygon::from_moves(moves);
+    let min_x = vertices.iter().map(|p| p.x).min().unwrap_or_default();
+    let min_y = vertices.iter().map(|p| p.y).min().unwrap_or_default();
+    let max_x = vertices.iter().map(|p| p.x).max().unwrap_or_default();
+    let max_y = vertices.iter().map(|p| p.y).max().unwrap_or_default();
+    let (width, height) = (max_x - min_x + 3, max_y - min_y + 3);
+    if width * height > MAX_DRAWING_CELLS {
+        return None;
+    }
+    let mut grid = Grid::init(width as usize, height as usize, '.');
+    let mut position = Point::from(1 - min_x, 1 - min_y);
+    for (direction, len) in moves {
+        for _ in 0..*len {
+            position = position + direction.delta();
+            if let Some(coord) = grid.coord_of(&position) {
+                grid.set(&coord, '#');
+            }
         }
-        if pos.y > max_up {
-            max_up = pos.y;
+    }
+    let outside = grid.get_edge_reachable_pos_set(&'#');
+    for (pos, c) in grid.data.iter_mut().enumerate() {
+        if *c == '.' && !outside.contains(&pos) {
+            *c = '~';
         }
     }
+    Some(grid)
+}
 
-    Coord::from(max_right - min_left, max_up - min_down)
+// cubic meters dug out, the trench itself plus everything it encloses
+pub fn process(moves: &[Move]) -> usize {
+    let vertices = polygon::from_moves(moves);
+    polygon::lattice_points(&vertices) as usize
 }
 
-fn get_line_components(line: &str) -> Result<(Direction, usize)> {
+fn get_line_components(line: &str) -> Result<Move> {
     let mut split = line.split(" ");
-    let dir = split.next().unwrap().parse::<Direction>().unwrap();
-    let len = split.next().unwrap().parse::<usize>().unwrap();
+    let dir_text = split.next().unwrap_or_default();
+    let dir = dir_text
+        .parse::<Direction>()
+        .map_err(|e| AocError::parse(1, format!("{} [{}]", e, dir_text)))?;
+    let len_text = split.next().ok_or_else(|| {
+        AocError::parse(dir_text.len() + 1, "Expected a length after the direction")
+    })?;
+    let len = len_text
+        .parse::<usize>()
+        .map_err(|e| AocError::parse(column_of(line, len_text), e.to_string()))?;
+    Ok((dir, len))
+}
+
+// (#70c710) is 0x70c71 meters to the right, the last digit is the direction
+// 0 right, 1 down, 2 left and 3 up
+fn get_colour_components(line: &str) -> Result<Move> {
+    let colour = line
+        .split(" ")
+        .nth(2)
+        .ok_or_else(|| AocError::parse(line.len() + 1, "Expected a colour after the length"))?;
+    let hex = colour
+        .strip_prefix("(#")
+        .and_then(|c| c.strip_suffix(")"))
+        .filter(|h| h.len() == 6 && h.is_ascii())
+        .ok_or_else(|| {
+            AocError::parse(
+                column_of(line, colour),
+                format!("Expected a colour like (#70c710), found [{}]", colour),
+            )
+        })?;
+    let hex_column = column_of(line, hex);
+    let len = usize::from_str_radix(&hex[..5], 16)
+        .map_err(|e| AocError::parse(hex_column, format!("{} [{}]", e, &hex[..5])))?;
+    let dir = match &hex[5..] {
+        "0" => Direction::East,
+        "1" => Direction::South,
+        "2" => Direction::West,
+        "3" => Direction::North,
+        other => {
+            return Err(AocError::parse(
+                hex_column + 5,
+                format!("String not a valid direction [{}]", other),
+            )
+            .into())
+        }
+    };
     Ok((dir, len))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
-    const LINES_1: &str = "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)";
+    const LINES_1: &str = include_str!("../examples/day_18_1.txt");
 
     #[test]
     fn test_simple_input() {
-        let lines = utils::string_to_lines(LINES_1.to_string());
-        let result = process(&lines, 1);
-        assert_eq!(62, result.unwrap());
+        let input = Input::from(LINES_1);
+        let result = Day18::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(62), result.unwrap());
+    }
+
+    #[test]
+    fn test_simple_input_part_two() {
+        let input = Input::from(LINES_1);
+        let result = Day18::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(952408144115_usize), result.unwrap());
+    }
+
+    #[test]
+    fn test_colour_components() {
+        let (dir, len) = get_colour_components("R 6 (#70c710)").unwrap();
+        assert!(matches!(dir, Direction::East));
+        assert_eq!(461937, len);
+        let (dir, len) = get_colour_components("U 2 (#7a21e3)").unwrap();
+        assert!(matches!(dir, Direction::North));
+        assert_eq!(500254, len);
+
+        let err = get_colour_components("R 6 (#70c714)").unwrap_err();
+        let err = err.downcast_ref::<AocError>().unwrap();
+        assert!(matches!(err, AocError::Parse { column: 12, .. }));
+        assert!(get_colour_components("R 6").is_err());
+        assert!(get_colour_components("R 6 #70c710").is_err());
     }
+
+    #[test]
+    fn test_draw_trench() -> Result<()> {
+        let input = Input::from(LINES_1);
+        let plan = Day18::parse(&input)?;
+        let drawing = Day18::render(&plan, DayPart::One)?.unwrap();
+        assert_eq!((9, 12), (drawing.width, drawing.height));
+        assert_eq!(38, drawing.count_eq(&'#'));
+        assert_eq!(62, drawing.count_eq(&'#') + drawing.count_eq(&'~'));
+        assert!(Day18::render(&plan, DayPart::Two)?.is_none());
+        Ok(())
+    }
+
     #[test]
-    fn test_bounds() {
-        let lines = utils::string_to_lines(LINES_1.to_string());
-        let result = get_bounds(&lines);
-        let e = Coord::from(7, 10);
-        assert_eq!(result.x, 6);
-        assert_eq!(result.y, 9);
+    fn test_plan_without_colours() {
+        let input = Input::from("R 2\nD 2\nL 2\nU 2");
+        assert_eq!(Answer::from(9), Day18::solve(&input, DayPart::One).unwrap());
+        let err = Day18::solve(&input, DayPart::Two).unwrap_err();
+        assert_eq!(
+            "Parse error at line 1, column 4: Expected a colour after the length",
+            err.to_string()
+        );
     }
 }
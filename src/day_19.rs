@@ -1,9 +1,19 @@
-use std::{cmp::Ordering, collections::HashMap, ops::Index, str::FromStr};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    ops::Index,
+    str::FromStr,
+};
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use regex::Regex;
 
+use crate::answer::Answer;
+use crate::error::{column_of, AocError, ParseContext};
+use crate::input::Input;
+use crate::ranges::{Hyperbox, Interval};
 use crate::re_utils;
+use crate::solution::{DayPart, Solution};
 
 #[derive(Debug)]
 enum Operator {
@@ -12,12 +22,12 @@ enum Operator {
 }
 
 impl FromStr for Operator {
-    type Err = String;
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
         Ok(match s {
             ">" => Operator::GT,
             "<" => Operator::LT,
-            _ => return Err(format!("Invalid operator {s}")),
+            _ => bail!("Invalid operator {s}"),
         })
     }
 }
@@ -45,7 +55,7 @@ impl Workflow {
                 "m" => part.m,
                 "a" => part.a,
                 "s" => part.s,
-                _ => todo!(),
+                _ => unreachable!("Step categories are validated when parsed"),
             };
             //println!("Comparing {} {:?} {}",value,  step.order, step.value);
             let predicate = match step.order {
@@ -59,33 +69,91 @@ impl Workflow {
 
         self.default_target.clone()
     }
+
+    // splits the box of ratings by where each part of it is sent to
+    fn split(&self, ratings: RatingBox) -> Vec<(String, RatingBox)> {
+        let mut result: Vec<(String, RatingBox)> = vec![];
+        let mut left = Some(ratings);
+        for step in &self.steps {
+            let Some(ratings) = left else {
+                break;
+            };
+            let dim = category_index(&step.part);
+            let (matched, rest) = match step.order {
+                Operator::LT => ratings.split_at(dim, step.value),
+                Operator::GT => {
+                    let (below, above) = ratings.split_at(dim, step.value + 1);
+                    (above, below)
+                }
+            };
+            if let Some(matched) = matched {
+                result.push((step.target.clone(), matched));
+            }
+            left = rest;
+        }
+        if let Some(ratings) = left {
+            result.push((self.default_target.clone(), ratings));
+        }
+        result
+    }
+}
+
+// x, m, a and s ratings, one dimension each
+type RatingBox = Hyperbox<4>;
+const MIN_RATING: usize = 1;
+const MAX_RATING: usize = 4000;
+
+fn category_index(category: &str) -> usize {
+    match category {
+        "x" => 0,
+        "m" => 1,
+        "a" => 2,
+        "s" => 3,
+        _ => unreachable!("Step categories are validated when parsed"),
+    }
 }
 
-impl FromStr for Workflow {
-    type Err = String;
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+// a step like `a<2006:qkq`
+fn step_regex() -> Result<Regex> {
+    Ok(Regex::new(r"^([xmas])([<>])(\d+):(\w+)$")?)
+}
+
+impl Workflow {
+    // `step_re` is the `step_regex`, compiled once for every workflow
+    fn parse(s: &str, step_re: &Regex) -> Result<Self> {
         //println!("Trying to parse {s}");
-        let re = Regex::new(r"(\w+)([<>])(\d+):(\w+)").unwrap();
+        let (id, rest) = s
+            .split_once('{')
+            .ok_or_else(|| AocError::parse(1, "Expected a workflow like `id{...}`"))?;
+        let body = rest
+            .strip_suffix('}')
+            .ok_or_else(|| AocError::parse(s.len(), "Expected a closing `}`"))?;
+        let (rules, default_target) = body.rsplit_once(',').unwrap_or(("", body));
+        if default_target.is_empty() {
+            bail!(AocError::parse(s.len(), "Expected a default target"));
+        }
         let mut steps: Vec<Step> = Vec::new();
-        for sp in s.split(r",") {
-            for (_, [part, operator, amount, target]) in re.captures_iter(sp).map(|c| c.extract()) {
-                //println!("found {part} {operator} {amount} {target}");
-                steps.push(Step {
-                    part: part.to_string(),
-                    value: amount.parse().unwrap(),
-                    target: target.to_string(),
-                    order: operator.parse::<Operator>().unwrap(),
-                });
-            }
+        for sp in rules.split(r",").filter(|sp| !sp.is_empty()) {
+            let Some(captures) = step_re.captures(sp) else {
+                bail!(AocError::parse(
+                    column_of(s, sp),
+                    format!("Invalid step [{}]", sp)
+                ));
+            };
+            let (_, [part, operator, amount, target]) = captures.extract();
+            //println!("found {part} {operator} {amount} {target}");
+            steps.push(Step {
+                part: part.to_string(),
+                value: amount.parse()?,
+                target: target.to_string(),
+                order: operator.parse::<Operator>()?,
+            });
         }
-        let id = s[..s.find(r"{").unwrap()].to_string();
-        let default_target = s.split(r",").last().unwrap();
-        let default_target = default_target[..default_target.len() - 1].to_string();
 
         Ok(Workflow {
-            id,
+            id: id.to_string(),
             steps,
-            default_target,
+            default_target: default_target.to_string(),
         })
     }
 }
@@ -105,78 +173,184 @@ impl Part {
 }
 
 impl FromStr for Part {
-    type Err = String;
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let nums = re_utils::parse_line_numbers(s).unwrap();
-        let (x, m, a, s) = (nums[0], nums[1], nums[2], nums[3]);
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let nums = re_utils::parse_line_numbers(s)?;
+        let [x, m, a, s] = nums[..] else {
+            bail!(AocError::parse(1, "Expected the x, m, a and s ratings"));
+        };
         Ok(Part { x, m, a, s })
     }
 }
 
 type WorkflowMap = HashMap<String, Workflow>;
 
-pub fn process(lines: &[String], part: usize) -> Result<usize> {
-    let mut map: WorkflowMap = HashMap::new();
-    for line in lines {
-        if line.is_empty() {
-            break;
+pub struct System {
+    workflows: WorkflowMap,
+    parts: Vec<Part>,
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: usize = 19;
+    type Parsed<'a> = System;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        // the workflows, a blank line and the parts
+        let mut blocks = input.blocks();
+        let workflow_lines = blocks.next().unwrap_or_default();
+        let part_lines = blocks.next().unwrap_or_default();
+        if let Some(extra) = blocks.next() {
+            bail!(AocError::parse(1, "Expected only workflows and parts")
+                .at_line(input.line_index(extra[0])));
         }
-        let workflow = line.parse::<Workflow>().unwrap();
-        map.insert(workflow.id.clone(), workflow);
-    }
-    let mut sum: usize = 0;
-    for line in lines {
-        if line.starts_with("{") {
-            let part = line.parse::<Part>().unwrap();
-            sum += process_workflow(&map, &part);
+        let step_re = step_regex()?;
+        let mut workflows: WorkflowMap = HashMap::new();
+        for line in workflow_lines {
+            let workflow = Workflow::parse(line, &step_re).at_line(input.line_index(line))?;
+            workflows.insert(workflow.id.clone(), workflow);
         }
+        let parts = part_lines
+            .into_iter()
+            .map(|line| line.parse::<Part>().at_line(input.line_index(line)))
+            .collect::<Result<Vec<Part>>>()?;
+        Ok(System { workflows, parts })
     }
-    Ok(sum)
+
+    fn part_one(system: &Self::Parsed<'_>) -> Result<Answer> {
+        let mut sum = 0;
+        for part in &system.parts {
+            sum += process_workflow(&system.workflows, part)?;
+        }
+        Ok(sum.into())
+    }
+
+    fn part_two(system: &Self::Parsed<'_>) -> Result<Answer> {
+        let all = Interval::new(MIN_RATING, MAX_RATING + 1);
+        Ok(count_accepted(&system.workflows, RatingBox::new([all; 4]))?.into())
+    }
+}
+
+// how many rating combinations in the box end up accepted
+fn count_accepted(map: &WorkflowMap, ratings: RatingBox) -> Result<usize> {
+    let mut accepted = 0;
+    // a box that went through more workflows than there are went through one
+    // of them twice, it would go round forever
+    let mut pending: Vec<(String, RatingBox, usize)> = vec![("in".to_string(), ratings, 0)];
+    while let Some((target, ratings, depth)) = pending.pop() {
+        match target.as_str() {
+            "A" => accepted += ratings.volume(),
+            "R" => continue,
+            _ => {
+                if depth >= map.len() {
+                    bail!("The workflows loop back through [{}]", target);
+                }
+                let workflow = map
+                    .get(&target)
+                    .ok_or_else(|| anyhow!("Unknown workflow [{}]", target))?;
+                pending.extend(
+                    workflow
+                        .split(ratings)
+                        .into_iter()
+                        .map(|(next, ratings)| (next, ratings, depth + 1)),
+                );
+            }
+        }
+    }
+    Ok(accepted)
 }
 
-fn process_workflow(map: &WorkflowMap, part: &Part) -> usize {
+fn process_workflow(map: &WorkflowMap, part: &Part) -> Result<usize> {
     let mut target = "in".to_string();
+    let mut visited: HashSet<String> = HashSet::new();
 
     loop {
         //println!("Processing target workflow {target}");
-        let workflow = map.get(&target).unwrap();
+        let workflow = map
+            .get(&target)
+            .ok_or_else(|| anyhow!("Unknown workflow [{}]", target))?;
+        if !visited.insert(target) {
+            bail!("The workflows loop back through [{}]", workflow.id);
+        }
         target = workflow.process(part);
 
         match target.as_str() {
-            "A" => return part.sum(),
-            "R" => return 0,
+            "A" => return Ok(part.sum()),
+            "R" => return Ok(0),
             _ => continue,
         }
     }
-    0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
-    const INPUT_1: &str = "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=787,m=2655,a=1222,s=2876}
-{x=1679,m=44,a=2067,s=496}
-{x=2036,m=264,a=79,s=2244}
-{x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}";
+    const INPUT_1: &str = include_str!("../examples/day_19_1.txt");
 
     #[test]
     fn test_simple_input() {
-        let lines = utils::string_to_lines(INPUT_1.to_string());
-        let result = process(&lines, 1);
-        assert_eq!(19114, result.unwrap());
+        let input = Input::from(INPUT_1);
+        let result = Day19::solve(&input, DayPart::One);
+        assert_eq!(Answer::from(19114), result.unwrap());
+        let result = Day19::solve(&input, DayPart::Two);
+        assert_eq!(Answer::from(167409079868000_u64), result.unwrap());
+    }
+
+    #[test]
+    fn test_split() -> Result<()> {
+        let workflow = Workflow::parse("px{a<2006:qkq,m>2090:A,rfg}", &step_regex()?)?;
+        let all = RatingBox::new([Interval::new(1, 4001); 4]);
+        let split = workflow.split(all);
+        let targets: Vec<&str> = split.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(vec!["qkq", "A", "rfg"], targets);
+        assert_eq!(Interval::new(1, 2006), split[0].1.dims[2]);
+        assert_eq!(Interval::new(2091, 4001), split[1].1.dims[1]);
+        assert_eq!(Interval::new(1, 2091), split[2].1.dims[1]);
+        let volume: usize = split.iter().map(|(_, r)| r.volume()).sum();
+        assert_eq!(all.volume(), volume);
+        Ok(())
+    }
+
+    #[test]
+    fn test_workflow_loop() {
+        let input = Input::from("in{a<5:x,R}\nx{in}\n\n{x=1,m=2,a=3,s=4}");
+        for part in [DayPart::One, DayPart::Two] {
+            let err = Day19::solve(&input, part).unwrap_err();
+            assert!(err
+                .to_string()
+                .starts_with("The workflows loop back through"));
+        }
+        // going through the same workflow on two branches is not a loop
+        let input = Input::from("in{a<5:x,y}\nx{z}\ny{z}\nz{A}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(
+            Answer::from(10),
+            Day19::solve(&input, DayPart::One).unwrap()
+        );
+        let all = 4000_usize.pow(4);
+        assert_eq!(
+            Answer::from(all),
+            Day19::solve(&input, DayPart::Two).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_workflow() {
+        let input = Input::from("in{a<2006:qkq,b>2090:A,rfg}");
+        let err = Day19::solve(&input, DayPart::One).unwrap_err();
+        assert_eq!(
+            "Parse error at line 1, column 15: Invalid step [b>2090:A]",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_part() {
+        let input = Input::from("in{A}\n\n{x=787,m=2655,a=1222}");
+        let err = Day19::solve(&input, DayPart::One).unwrap_err();
+        assert_eq!(
+            "Parse error at line 3, column 1: Expected the x, m, a and s ratings",
+            err.to_string()
+        );
     }
 }
@@ -1,16 +1,22 @@
+use crate::answer::Answer;
+use crate::day_2_2;
+use crate::error::{column_of, AocError, ParseContext};
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::Result;
 use regex::Regex;
 
 #[allow(unused)]
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum Color {
     Red(usize),
     Blue(usize),
     Green(usize),
 }
 
-pub struct RGB(usize, usize, usize);
+pub struct Rgb(usize, usize, usize);
 
-impl RGB {
+impl Rgb {
     pub fn red(&self) -> usize {
         self.0
     }
@@ -28,46 +34,116 @@ impl RGB {
         }
     }
 }
-pub fn score_lines(lines: Vec<String>) {
-    let limits = RGB(12, 13, 14);
-    let mut sum = 0;
-    for line in lines {
-        sum += score(&limits, line);
+
+// a game and every cube count drawn in it
+#[derive(Debug)]
+pub struct Game {
+    pub id: usize,
+    pub draws: Vec<Color>,
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: usize = 2;
+    type Parsed<'a> = Vec<Game>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_games(&input.lines())
+    }
+
+    fn part_one(games: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(sum_possible(games).into())
+    }
+
+    fn part_two(games: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_2_2::sum_powers(games).into())
     }
-    println!("Final score [{}]", sum);
 }
 
-pub fn score(limits: &RGB, line: String) -> usize {
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(sum_possible(&parse_games(lines)?))
+}
+
+// ids of the games possible with only 12 red, 13 green and 14 blue cubes
+fn sum_possible(games: &[Game]) -> usize {
+    let limits = Rgb(12, 13, 14);
+    games
+        .iter()
+        .filter(|game| !game.draws.iter().any(|color| limits.is_greater(*color)))
+        .map(|game| game.id)
+        .sum()
+}
+
+// every game of the record, lines that are not games are skipped
+pub fn parse_games(lines: &[&str]) -> Result<Vec<Game>> {
+    let mut games = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(game) = parse_game(line).at_line(i)? {
+            games.push(game);
+        }
+    }
+    Ok(games)
+}
+
+fn parse_game(line: &str) -> Result<Option<Game>> {
     // println!("Processing [{}]", line);
     if !line.starts_with("Game") {
-        return 0;
+        return Ok(None);
     }
-    let game_num_re = Regex::new(r"Game (?P<game>\d+):").unwrap();
-    let captures = game_num_re.captures(&line).unwrap();
-    let game_num = captures["game"].parse::<usize>().unwrap();
-    let second_part = line.split(":").nth(1).unwrap();
-    let part_re = Regex::new(r"(?P<num>\d+) (?P<color>[a-z]+)").unwrap();
+    let game_num_re = Regex::new(r"Game (?P<game>\d+):")?;
+    let captures = game_num_re
+        .captures(line)
+        .ok_or_else(|| AocError::parse(1, "Expected a `Game <id>:` header"))?;
+    let id = captures["game"].parse::<usize>()?;
+    let mut draws = vec![];
+    let second_part = &line[captures[0].len()..];
+    let part_re = Regex::new(r"(?P<num>\d+) (?P<color>[a-z]+)")?;
     for part in second_part.split(";") {
         for smaller_part in part.split(",") {
             // println!("Part [{}]", smaller_part);
-            let capture = part_re.captures(smaller_part).unwrap();
-            let num = capture["num"].parse::<usize>().unwrap();
+            let column = column_of(line, smaller_part);
+            let capture = part_re.captures(smaller_part).ok_or_else(|| {
+                AocError::parse(
+                    column,
+                    format!("Expected `<count> <color>` in [{}]", smaller_part),
+                )
+            })?;
+            let num = capture["num"].parse::<usize>()?;
             let color_name = capture["color"].to_string();
-            let color = to_color(&color_name, num);
+            let color = to_color(&color_name, num).ok_or_else(|| {
+                AocError::parse(column, format!("Unknown color [{}]", color_name))
+            })?;
             // println!("Part [{:?}]", color);
-            if limits.is_greater(color) {
-                return 0;
-            }
+            draws.push(color);
         }
     }
-    game_num
+    Ok(Some(Game { id, draws }))
 }
 
-pub fn to_color(name: &str, num: usize) -> Color {
+pub fn to_color(name: &str, num: usize) -> Option<Color> {
     match name {
-        "red" => Color::Red(num),
-        "green" => Color::Green(num),
-        "blue" => Color::Blue(num),
-        _ => panic!("No color with name {}", name),
+        "red" => Some(Color::Red(num)),
+        "green" => Some(Color::Green(num)),
+        "blue" => Some(Color::Blue(num)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_input() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
+        assert_eq!(8, result.unwrap());
     }
 }
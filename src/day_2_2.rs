@@ -1,17 +1,11 @@
-use regex::Regex;
+use anyhow::Result;
 use std::cmp;
 
-#[allow(unused)]
-#[derive(Debug)]
-pub enum Color {
-    Red(usize),
-    Blue(usize),
-    Green(usize),
-}
+use crate::day_2_1::{parse_games, Color, Game};
 
-pub struct RGB(usize, usize, usize);
+pub struct Rgb(usize, usize, usize);
 
-impl RGB {
+impl Rgb {
     pub fn red(&self) -> usize {
         self.0
     }
@@ -29,44 +23,39 @@ impl RGB {
         }
     }
 }
-pub fn score_lines(lines: Vec<String>) {
-    let mut sum = 0;
-    for line in lines {
-        sum += score(line);
-    }
-    println!("Final score [{}]", sum);
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(sum_powers(&parse_games(lines)?))
 }
 
-pub fn score(line: String) -> usize {
-    // println!("Processing [{}]", line);
-    if !line.starts_with("Game") {
-        return 0;
-    }
-    let mut limits = RGB(0, 0, 0);
-    let game_num_re = Regex::new(r"Game (?P<game>\d+):").unwrap();
-    let captures = game_num_re.captures(&line).unwrap();
-    let _game_num = captures["game"].parse::<usize>().unwrap();
-    let second_part = line.split(":").nth(1).unwrap();
-    let part_re = Regex::new(r"(?P<num>\d+) (?P<color>[a-z]+)").unwrap();
-    for part in second_part.split(";") {
-        for smaller_part in part.split(",") {
-            // println!("Part [{}]", smaller_part);
-            let capture = part_re.captures(smaller_part).unwrap();
-            let num = capture["num"].parse::<usize>().unwrap();
-            let color_name = capture["color"].to_string();
-            let color = to_color(&color_name, num);
-            // println!("Part [{:?}]", color);
-            limits.update(color);
-        }
-    }
-    limits.0 * limits.1 * limits.2
+// the fewest cubes of each color a game could be played with, multiplied
+pub fn sum_powers(games: &[Game]) -> usize {
+    games
+        .iter()
+        .map(|game| {
+            let mut limits = Rgb(0, 0, 0);
+            for color in &game.draws {
+                limits.update(*color);
+            }
+            limits.0 * limits.1 * limits.2
+        })
+        .sum()
 }
 
-pub fn to_color(name: &str, num: usize) -> Color {
-    match name {
-        "red" => Color::Red(num),
-        "green" => Color::Green(num),
-        "blue" => Color::Blue(num),
-        _ => panic!("No color with name {}", name),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+
+    #[test]
+    fn test_simple_input() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
+        assert_eq!(2286, result.unwrap());
     }
 }
@@ -1,105 +1,126 @@
-use regex::Regex;
+use crate::answer::Answer;
+use crate::day_3_2;
+use crate::error::AocError;
+use crate::input::Input;
+use crate::prelude::{Coord, Grid};
+use crate::solution::Solution;
+use anyhow::Result;
 
-#[allow(unused)]
+// a number in the schematic, `len` digits long starting at `start`
 #[derive(Debug)]
-struct Position {
-    row: i64,
-    start: i64,
-    end: i64,
+pub struct Part {
+    pub start: Coord,
+    pub len: usize,
+    pub value: usize,
 }
 
-#[derive(Debug)]
-struct Part {
-    pos: Position,
-    value: i64,
+impl Part {
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.len).map(|i| Coord::from(self.start.x + i, self.start.y))
+    }
 }
 
-#[derive(Debug)]
-struct Symbol {
-    symbol: char,
-    pos: Position,
+// the engine schematic and every number in it
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub parts: Vec<Part>,
 }
 
-struct Row {
-    row: i64,
-    symbols: Vec<Symbol>,
-    parts: Vec<Part>,
-}
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: usize = 3;
+    type Parsed<'a> = Schematic;
 
-impl Position {
-    pub fn overlaps_columns(&self, other: &Position) -> bool {
-        self.is_between(other.start)
-            || self.is_between(other.start - 1)
-            || self.is_between(other.start + 1)
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_schematic(&input.lines())
     }
 
-    fn is_between(&self, n: i64) -> bool {
-        n >= self.start && n <= self.end
+    fn part_one(schematic: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(sum_part_numbers(schematic).into())
     }
-}
 
-impl Part {
-    pub fn is_overlap_symbol(&self, symbol: &Symbol) -> bool {
-        let diff = self.pos.row - symbol.pos.row;
-        diff >= -1 && diff <= 1 && self.pos.overlaps_columns(&symbol.pos)
+    fn part_two(schematic: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_3_2::sum_gear_ratios(schematic).into())
     }
 }
 
-pub fn process_lines(lines: Vec<String>) {
-    let mut all_parts: Vec<Part> = vec![];
-    let mut all_symbol: Vec<Symbol> = vec![];
-    let mut count = 0 as i64;
-    for line in lines {
-        let (mut parts, mut symbols) = parse_line(line, count);
-        all_parts.append(&mut parts);
-        all_symbol.append(&mut symbols);
-        count += 1;
-    }
-    let mut result = 0 as i64;
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(sum_part_numbers(&parse_schematic(lines)?))
+}
 
-    for part in all_parts {
-        for symbol in &all_symbol {
-            if part.is_overlap_symbol(symbol) {
-                result += part.value;
-                println!("Matching {:?} with {:?}", symbol, part);
-                break;
-            }
-        }
-    }
-    println!("Result is [{}]", result);
+pub fn parse_schematic(lines: &[&str]) -> Result<Schematic> {
+    let grid: Grid<char> = Grid::from_chars(lines)?;
+    let parts = find_parts(&grid)?;
+    Ok(Schematic { grid, parts })
 }
 
-fn parse_line(line: String, row: i64) -> (Vec<Part>, Vec<Symbol>) {
-    let part_re = Regex::new(r"(\d+)").unwrap();
-    let mut parts: Vec<Part> = vec![];
-    let mut symbols: Vec<Symbol> = vec![];
-    for part in part_re.find_iter(&line) {
-        parts.push(Part {
-            pos: Position {
-                start: part.start() as i64,
-                end: part.end() as i64 - 1 as i64,
-                row,
-            },
-            value: part.as_str().parse::<i64>().unwrap(),
-        });
-    }
+// the numbers next to a symbol, diagonals included
+fn sum_part_numbers(schematic: &Schematic) -> usize {
+    let grid = &schematic.grid;
+    schematic
+        .parts
+        .iter()
+        .filter(|part| {
+            part.coords()
+                .any(|c| grid.neighbors8(&c).any(|(_, n)| is_symbol(*n)))
+        })
+        .map(|part| part.value)
+        .sum()
+}
 
-    for (i, c) in line.chars().enumerate() {
-        if is_symbol(c) {
-            symbols.push(Symbol {
-                pos: Position {
-                    start: i as i64,
-                    end: i as i64,
-                    row,
-                },
-                symbol: c,
+// every number in the schematic, in reading order
+pub fn find_parts(grid: &Grid<char>) -> Result<Vec<Part>> {
+    let mut parts: Vec<Part> = vec![];
+    for y in 0..grid.height {
+        let mut x = 0;
+        while x < grid.width {
+            let start = Coord::from(x, y);
+            let mut digits = String::new();
+            while x < grid.width && grid.data[y * grid.width + x].is_ascii_digit() {
+                digits.push(grid.data[y * grid.width + x]);
+                x += 1;
+            }
+            if digits.is_empty() {
+                x += 1;
+                continue;
+            }
+            let value = digits
+                .parse::<usize>()
+                .map_err(|e| AocError::parse(start.x + 1, e.to_string()).at_line(y))?;
+            parts.push(Part {
+                start,
+                len: digits.len(),
+                value,
             });
         }
     }
-
-    (parts, symbols)
+    Ok(parts)
 }
 
 fn is_symbol(c: char) -> bool {
     !c.is_numeric() && c != '.' && c != ' '
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_input() {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
+        assert_eq!(4361, result.unwrap());
+    }
+}
@@ -1,42 +1,100 @@
+use crate::answer::Answer;
+use crate::day_4_2;
+use crate::error::{column_of, AocError, ParseContext};
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::Result;
 use regex::Regex;
 use std::collections::HashSet;
 
-#[allow(unused)]
+pub struct Day4;
 
-pub fn process_lines(lines: Vec<String>) {
-    let mut result = 0;
+impl Solution for Day4 {
+    const DAY: usize = 4;
+    // how many winning numbers each card has
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_cards(&input.lines())
+    }
 
-    for line in lines {
-        result += parse_line(line);
+    fn part_one(win_counts: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(sum_points(win_counts)?.into())
     }
 
-    println!("Result [{}]", result);
+    fn part_two(win_counts: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_4_2::count_cards(win_counts).into())
+    }
 }
 
-fn parse_line(line: String) -> usize {
-    //println!("Parsing line [{}]", line);
-    if line.len() == 0 {
-        return 0;
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    sum_points(&parse_cards(lines)?)
+}
+
+// a card is worth 1 point for its first winning number, doubled for every
+// other one
+fn sum_points(win_counts: &[usize]) -> Result<usize> {
+    let mut result = 0;
+    for win_count in win_counts {
+        if *win_count > 0 {
+            result += 2_usize.pow((win_count - 1).try_into()?);
+        }
     }
-    let mut parts = line.split(":").nth(1).unwrap().split("|");
+    Ok(result)
+}
 
-    let win_text = parts.next().unwrap();
-    let own_text = parts.next().unwrap();
+// the winning numbers count of every card, blank lines are skipped
+pub fn parse_cards(lines: &[&str]) -> Result<Vec<usize>> {
+    let mut win_counts = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        win_counts.push(parse_line(line).at_line(i)?);
+    }
+    Ok(win_counts)
+}
+
+fn parse_line(line: &str) -> Result<usize> {
+    //println!("Parsing line [{}]", line);
+    let (_, numbers) = line
+        .split_once(':')
+        .ok_or_else(|| AocError::parse(1, "Expected a `Card <id>:` header"))?;
+    let (win_text, own_text) = numbers.split_once('|').ok_or_else(|| {
+        AocError::parse(
+            column_of(line, numbers),
+            "Expected winning and own numbers separated by `|`",
+        )
+    })?;
 
-    let num_re = Regex::new(r"(\d+)").unwrap();
+    let num_re = Regex::new(r"(\d+)")?;
     let mut win: HashSet<usize> = HashSet::new(); // assuming no repeating numbers
     let mut own: HashSet<usize> = HashSet::new(); // assuming no repeating numbers
-    for num in num_re.find_iter(&win_text) {
-        win.insert(num.as_str().parse::<usize>().unwrap());
+    for num in num_re.find_iter(win_text) {
+        win.insert(num.as_str().parse::<usize>()?);
     }
-    for num in num_re.find_iter(&own_text) {
-        own.insert(num.as_str().parse::<usize>().unwrap());
+    for num in num_re.find_iter(own_text) {
+        own.insert(num.as_str().parse::<usize>()?);
     }
     let intersection = own.intersection(&win);
-    let win_count = intersection.count();
-    if win_count == 0 {
-        return 0;
-    }
+    Ok(intersection.count())
+}
 
-    2_i32.pow((win_count - 1).try_into().unwrap()) as usize
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_input() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
+        assert_eq!(13, result.unwrap());
+    }
 }
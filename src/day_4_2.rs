@@ -1,65 +1,45 @@
-use regex::Regex;
-use std::collections::HashSet;
+use anyhow::Result;
 
-#[allow(unused)]
-#[derive(Default)]
-struct Card {
-    number: usize,
-    win_count: usize,
-    card_count: usize,
+use crate::day_4_1::parse_cards;
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(count_cards(&parse_cards(lines)?))
 }
 
-pub fn process_lines(lines: Vec<String>) {
-    let mut cards: Vec<Card> = vec![];
-    for (i, line) in lines.iter().enumerate() {
-        if line.len() == 0 {
-            continue;
-        }
-        println!("Parse line [{}], [{}]", i, line);
-        let win_count = parse_line(line);
-        let card = Card {
-            number: i,
-            win_count,
-            card_count: 1,
-        };
-        cards.push(card);
-    }
-    println!("Total cards [{}]", cards.len());
-    let mut card_counts: Vec<usize> = vec![0; cards.len()];
+// every card wins a copy of the next cards, one per winning number
+pub fn count_cards(win_counts: &[usize]) -> usize {
+    let mut card_counts: Vec<usize> = vec![0; win_counts.len()];
 
     let mut result = 0;
-    for i in 0..cards.len() {
+    for i in 0..win_counts.len() {
         card_counts[i] += 1;
         result += card_counts[i];
-        for j in i + 1..cards[i].win_count + i + 1 {
-            if j < cards.len() {
+        for j in i + 1..win_counts[i] + i + 1 {
+            if j < win_counts.len() {
                 card_counts[j] += card_counts[i];
             }
         }
     }
 
-    println!("Result [{}]", result);
+    result
 }
 
-fn parse_line(line: &str) -> usize {
-    //println!("Parsing line [{}]", line);
-    if line.len() == 0 {
-        return 0;
-    }
-    let mut parts = line.split(":").nth(1).unwrap().split("|");
-
-    let win_text = parts.next().unwrap();
-    let own_text = parts.next().unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
 
-    let num_re = Regex::new(r"(\d+)").unwrap();
-    let mut win: HashSet<usize> = HashSet::new(); // assuming no repeating numbers
-    let mut own: HashSet<usize> = HashSet::new(); // assuming no repeating numbers
-    for num in num_re.find_iter(&win_text) {
-        win.insert(num.as_str().parse::<usize>().unwrap());
-    }
-    for num in num_re.find_iter(&own_text) {
-        own.insert(num.as_str().parse::<usize>().unwrap());
+    #[test]
+    fn test_simple_input() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
+        assert_eq!(30, result.unwrap());
     }
-    let intersection = own.intersection(&win);
-    intersection.count()
 }
@@ -1,99 +1,94 @@
+use crate::answer::Answer;
+use crate::day_5_2;
+use crate::error::{AocError, ParseContext};
+use crate::input::Input;
+use crate::ranges::{Mapping, PiecewiseMap};
 use crate::re_utils;
+use crate::solution::Solution;
 
 use anyhow::Result;
 
-struct Mapping {
-    source: usize,
-    destination: usize,
-    range: usize,
+// the seeds and the maps from seed to location, in the order they are applied
+pub struct Almanac {
+    pub seeds: Vec<usize>,
+    pub maps: Vec<PiecewiseMap>,
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut seed_to_soil_map: Vec<Mapping> = vec![];
-    let mut soil_to_fertilizer: Vec<Mapping> = vec![];
-    let mut fertilizer_to_water: Vec<Mapping> = vec![];
-    let mut water_to_light: Vec<Mapping> = vec![];
-    let mut light_to_temperature: Vec<Mapping> = vec![];
-    let mut temperature_to_humidity: Vec<Mapping> = vec![];
-    let mut humidity_to_location: Vec<Mapping> = vec![];
-
-    let mut line_it = lines.iter();
-    let initial_seeds = re_utils::parse_line_numbers(line_it.next().unwrap())?;
-    println!("Initial seeds [{:?}]", initial_seeds);
-
-    loop {
-        let line = line_it.next();
-        if line.is_none() {
-            break;
-        }
-        let header = line.unwrap();
-        if header.len() == 0 {
-            continue;
-        }
-        println!("Parsing header [{}]", header);
-        match &header[0..12] {
-            "seed-to-soil" => seed_to_soil_map = parse_map(&mut line_it)?,
-            "soil-to-fert" => soil_to_fertilizer = parse_map(&mut line_it)?,
-            "fertilizer-t" => fertilizer_to_water = parse_map(&mut line_it)?,
-            "water-to-lig" => water_to_light = parse_map(&mut line_it)?,
-            "light-to-tem" => light_to_temperature = parse_map(&mut line_it)?,
-            "temperature-" => temperature_to_humidity = parse_map(&mut line_it)?,
-            "humidity-to-" => humidity_to_location = parse_map(&mut line_it)?,
-            _ => break,
-        }
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: usize = 5;
+    type Parsed<'a> = Almanac;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_almanac(&input.lines())
     }
-    let mut result = usize::MAX;
-    for seed in initial_seeds {
-        let soil = find(&seed_to_soil_map, seed);
-        let fertilizer = find(&soil_to_fertilizer, soil);
-        let water = find(&fertilizer_to_water, fertilizer);
-        let light = find(&water_to_light, water);
-        let temperature = find(&light_to_temperature, light);
-        let humidity = find(&temperature_to_humidity, temperature);
-        let location = find(&humidity_to_location, humidity);
-        if location < result {
-            result = location;
-        }
+
+    fn part_one(almanac: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(lowest_location(almanac)?.into())
+    }
+
+    fn part_two(almanac: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_5_2::lowest_range_location(almanac)?.into())
     }
+}
 
-    Ok(result)
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    lowest_location(&parse_almanac(lines)?)
 }
 
-fn find(mapping: &Vec<Mapping>, source: usize) -> usize {
-    for map in mapping {
-        let upper = map.source + map.range;
-        if map.source <= source && upper >= source {
-            return map.destination + (source - map.source);
-        }
-    }
-    source
+fn lowest_location(almanac: &Almanac) -> Result<usize> {
+    //println!("Initial seeds [{:?}]", almanac.seeds);
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| almanac.maps.iter().fold(*seed, |value, map| map.map(value)))
+        .min()
+        .ok_or_else(|| AocError::Parse {
+            line: 1,
+            column: 1,
+            message: "Expected at least one seed".to_string(),
+        })
+        .map_err(Into::into)
 }
 
-fn parse_map(it: &mut std::slice::Iter<String>) -> Result<Vec<Mapping>> {
-    let mut result: Vec<Mapping> = vec![];
-    loop {
-        match it.next() {
-            Some(line) => {
-                if line.len() == 0 {
-                    break;
-                }
-                let (destination, source, range) = re_utils::parse_3(line)?;
-                result.push(Mapping {
-                    source,
-                    destination,
-                    range,
-                });
+pub fn parse_almanac(lines: &[&str]) -> Result<Almanac> {
+    let mut line_it = lines.iter().enumerate();
+    let (_, seeds_line) = line_it.next().ok_or(AocError::EmptyInput)?;
+    let initial_seeds = re_utils::parse_line_numbers(seeds_line).at_line(0)?;
+
+    let mut maps: Vec<PiecewiseMap> = vec![];
+    let mut mappings: Vec<Mapping> = vec![];
+    for (i, line) in line_it {
+        if line.is_empty() {
+            continue;
+        }
+        if line.ends_with("map:") {
+            //println!("Parsing header [{}]", line);
+            if !mappings.is_empty() {
+                maps.push(PiecewiseMap::new(std::mem::take(&mut mappings)));
             }
-            None => break,
+            continue;
         }
+        let (destination, source, range) = re_utils::parse_3(line).at_line(i)?;
+        mappings.push(Mapping {
+            source,
+            destination,
+            range,
+        });
+    }
+    if !mappings.is_empty() {
+        maps.push(PiecewiseMap::new(mappings));
     }
-    Ok(result)
+    Ok(Almanac {
+        seeds: initial_seeds,
+        maps,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
     #[test]
     fn test_simple_input() {
         let input = "seeds: 79 14 55 13
@@ -130,8 +125,9 @@ humidity-to-location map:
 60 56 37
 56 93 4
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(35, result.unwrap());
     }
 }
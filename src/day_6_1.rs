@@ -1,17 +1,58 @@
+use crate::answer::Answer;
+use crate::day_6_2;
+use crate::error::AocError;
+use crate::input::Input;
 use crate::re_utils;
+use crate::solution::Solution;
 use anyhow::Result;
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut line_it = lines.into_iter();
-    let times = re_utils::parse_line_numbers(&line_it.next().unwrap())?;
-    let distances = re_utils::parse_line_numbers(&line_it.next().unwrap())?;
+// the time and record distance of every race, in the order on the sheet
+pub struct Races {
+    pub times: Vec<usize>,
+    pub distances: Vec<usize>,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: usize = 6;
+    type Parsed<'a> = Races;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_races(&input.lines())
+    }
+
+    fn part_one(races: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(multiply_win_counts(races).into())
+    }
+
+    fn part_two(races: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_6_2::single_race_win_count(races)?.into())
+    }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    Ok(multiply_win_counts(&parse_races(lines)?))
+}
+
+pub fn parse_races(lines: &[&str]) -> Result<Races> {
+    let mut line_it = lines.iter();
+    let times = re_utils::parse_line_numbers(line_it.next().ok_or(AocError::EmptyInput)?)?;
+    let distances = re_utils::parse_line_numbers(
+        line_it
+            .next()
+            .ok_or_else(|| AocError::parse(1, "Expected a `Distance:` line"))?,
+    )?;
+    Ok(Races { times, distances })
+}
 
-    let result = times
+fn multiply_win_counts(races: &Races) -> usize {
+    races
+        .times
         .iter()
-        .enumerate()
-        .map(|(i, time)| calculate_win_count(*time, distances[i]))
-        .fold(1, |acc, e| acc * e);
-    Ok(result)
+        .zip(races.distances.iter())
+        .map(|(time, distance)| calculate_win_count(*time, *distance))
+        .product::<usize>()
 }
 
 fn calculate_win_count(time: usize, distance: usize) -> usize {
@@ -31,14 +72,14 @@ fn calculate_distance(time: usize, pressed_time: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
     #[test]
     fn test_simple_input() {
         let input = "Time:      7  15   30
 Distance:  9  40  200
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(288, result.unwrap());
     }
 }
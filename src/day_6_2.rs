@@ -1,17 +1,23 @@
-use crate::re_utils;
+use crate::day_6_1::{parse_races, Races};
 use anyhow::Result;
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut line_it = lines.into_iter();
-    let times = re_utils::parse_line_numbers(&line_it.next().unwrap().replace(" ", ""))?;
-    let distances = re_utils::parse_line_numbers(&line_it.next().unwrap().replace(" ", ""))?;
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    single_race_win_count(&parse_races(lines)?)
+}
+
+// the spaces on the sheet are bad kerning, all the numbers are one race
+pub fn single_race_win_count(races: &Races) -> Result<usize> {
+    let time = join_digits(&races.times)?;
+    let distance = join_digits(&races.distances)?;
+    Ok(calculate_win_count(time, distance))
+}
 
-    let result = times
+fn join_digits(numbers: &[usize]) -> Result<usize> {
+    Ok(numbers
         .iter()
-        .enumerate()
-        .map(|(i, time)| calculate_win_count(*time, distances[i]))
-        .fold(1, |acc, e| acc * e);
-    Ok(result)
+        .map(|n| n.to_string())
+        .collect::<String>()
+        .parse::<usize>()?)
 }
 
 fn calculate_win_count(time: usize, distance: usize) -> usize {
@@ -31,14 +37,15 @@ fn calculate_distance(time: usize, pressed_time: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
+    use crate::input::Input;
     #[test]
     fn test_simple_input() {
         let input = "Time:      7  15   30
 Distance:  9  40  200
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(71503, result.unwrap());
     }
 }
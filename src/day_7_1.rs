@@ -1,7 +1,12 @@
-use anyhow::Result;
+use crate::answer::Answer;
+use crate::day_7_2;
+use crate::error::{AocError, ParseContext};
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 use std::{cmp::Ordering, collections::HashMap};
 
-#[derive(Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Debug, Eq, PartialEq)]
 enum Hand {
     FiveOfAKind(String),
     FourOfAKind(String),
@@ -12,7 +17,7 @@ enum Hand {
     HighCard(String),
 }
 
-#[derive(Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Debug, Eq, PartialEq)]
 struct Bet {
     hand: Hand,
     bid: usize,
@@ -55,6 +60,11 @@ impl Ord for Bet {
         self.hand.cmp(&other.hand)
     }
 }
+impl PartialOrd for Bet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
         let av = self.get_value();
@@ -68,11 +78,17 @@ impl Ord for Hand {
         Ordering::Less
     }
 }
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
 fn compare_hand_text(a: &str, b: &str) -> Ordering {
     for (ca, cb) in a.chars().zip(b.chars()) {
-        let va = char_value(ca);
-        let vb = char_value(cb);
+        // cards are validated when the line is parsed
+        let va = char_value(ca).unwrap_or(0);
+        let vb = char_value(cb).unwrap_or(0);
         if va > vb {
             return Ordering::Greater;
         }
@@ -83,30 +99,54 @@ fn compare_hand_text(a: &str, b: &str) -> Ordering {
     Ordering::Equal
 }
 
-fn char_value(c: char) -> usize {
+fn char_value(c: char) -> Option<usize> {
     if let Some(d) = c.to_digit(10) {
-        return d as usize;
+        return Some(d as usize);
     }
     match c {
-        'T' => 10,
-        'J' => 11,
-        'Q' => 12,
-        'K' => 13,
-        'A' => 14,
-        _ => panic!("Wrong char"),
+        'T' => Some(10),
+        'J' => Some(11),
+        'Q' => Some(12),
+        'K' => Some(13),
+        'A' => Some(14),
+        _ => None,
     }
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut bets: Vec<Bet> = vec![];
-    for line in lines {
-        if line.len() == 0 {
-            continue;
-        }
-        bets.push(line_to_bet(&line)?);
+// a hand as dealt and its bid, each part ranks the hands its own way
+#[derive(Debug)]
+pub struct Play<'a> {
+    pub hand: &'a str,
+    pub bid: usize,
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: usize = 7;
+    type Parsed<'a> = Vec<Play<'a>>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_plays(&input.lines())
+    }
+
+    fn part_one(plays: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(total_winnings(plays)?.into())
+    }
+
+    fn part_two(plays: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_7_2::total_winnings(plays)?.into())
     }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    total_winnings(&parse_plays(lines)?)
+}
+
+fn total_winnings(plays: &[Play]) -> Result<usize> {
+    let mut bets: Vec<Bet> = plays.iter().map(play_to_bet).collect::<Result<_>>()?;
     //println!("Bets before [{:?}]", bets);
-    bets.sort_by(|a, b| a.cmp(b));
+    bets.sort();
     //println!("Bets after [{:?}]", bets);
     let result = bets
         .iter()
@@ -118,18 +158,47 @@ pub fn process_lines(lines: Vec<String>) -> Result<usize> {
     Ok(result)
 }
 
-fn line_to_bet(line: &str) -> Result<Bet> {
+// every hand and bid, blank lines are skipped
+pub fn parse_plays<'a>(lines: &[&'a str]) -> Result<Vec<Play<'a>>> {
+    let mut plays = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        plays.push(parse_play(line).at_line(i)?);
+    }
+    Ok(plays)
+}
+
+fn parse_play(line: &str) -> Result<Play<'_>> {
     //println!("Parsing line [{}]", line);
-    let mut parts = line.split(" ").into_iter();
+    let mut parts = line.split(" ");
     ////println!("Parts [{:?}]", parts);
-    let hand_text = parts.next().unwrap();
-    let bid = parts.next().unwrap().parse::<usize>()?;
-    Ok(Bet {
-        hand: text_to_hand(hand_text)?,
+    let hand_text = parts.next().unwrap_or_default();
+    if let Some((i, c)) = hand_text
+        .chars()
+        .enumerate()
+        .find(|(_, c)| char_value(*c).is_none())
+    {
+        bail!(AocError::parse(i + 1, format!("Wrong card [{}]", c)));
+    }
+    let bid = parts
+        .next()
+        .ok_or_else(|| AocError::parse(hand_text.len() + 1, "Expected a bid after the hand"))?
+        .parse::<usize>()?;
+    Ok(Play {
+        hand: hand_text,
         bid,
     })
 }
 
+fn play_to_bet(play: &Play) -> Result<Bet> {
+    Ok(Bet {
+        hand: text_to_hand(play.hand)?,
+        bid: play.bid,
+    })
+}
+
 fn text_to_hand(text: &str) -> Result<Hand> {
     let s = text.to_string();
     let mut map: HashMap<char, usize> = HashMap::new();
@@ -140,7 +209,7 @@ fn text_to_hand(text: &str) -> Result<Hand> {
     if map.len() == 1 {
         return Ok(Hand::FiveOfAKind(s));
     }
-    let values: Vec<usize> = map.values().map(|a| *a).collect();
+    let values: Vec<usize> = map.values().copied().collect();
     //println!("Values [{:?}]", values);
     if values.contains(&4) {
         return Ok(Hand::FourOfAKind(s));
@@ -163,7 +232,6 @@ fn text_to_hand(text: &str) -> Result<Hand> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
     #[test]
     fn test_simple_input() {
         let input = "32T3K 765
@@ -172,8 +240,9 @@ KK677 28
 KTJJT 220
 QQQJA 483
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(6440, result.unwrap());
     }
 
@@ -202,4 +271,15 @@ QQQJA 483
         let b = Hand::ThreeOfAKind("T55J5".to_string());
         assert_eq!(Ordering::Greater, a.cmp(&b));
     }
+
+    #[test]
+    fn test_wrong_card() {
+        let input = Input::from("32T3K 765\nT55X5 684");
+        let lines = input.lines();
+        let err = process_lines(&lines).unwrap_err();
+        assert_eq!(
+            "Parse error at line 2, column 4: Wrong card [X]",
+            err.to_string()
+        );
+    }
 }
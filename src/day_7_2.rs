@@ -1,7 +1,8 @@
+use crate::day_7_1::{parse_plays, Play};
 use anyhow::{bail, Result};
 use std::{cmp::Ordering, collections::HashMap};
 
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 enum Hand {
     FiveOfAKind,
     FourOfAKind,
@@ -34,7 +35,7 @@ impl Hand {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 struct HandValue {
     hand_type: Hand,
     value: String,
@@ -49,7 +50,7 @@ impl HandValue {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Debug, Eq, PartialEq)]
 struct Bet {
     bid: usize,
     hand: HandValue,
@@ -82,6 +83,11 @@ impl Ord for HandValue {
         hand_cmp
     }
 }
+impl PartialOrd for HandValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
 impl Ord for Bet {
     fn cmp(&self, other: &Self) -> Ordering {
@@ -92,6 +98,11 @@ impl Ord for Bet {
         best_cmp
     }
 }
+impl PartialOrd for Bet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
         let av = self.get_value();
@@ -99,12 +110,18 @@ impl Ord for Hand {
         av.cmp(&bv)
     }
 }
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
 fn compare_hand_text(a: &str, b: &str) -> Ordering {
     //println!("Comparing [{}] [{}]", a, b);
     for (ca, cb) in a.chars().zip(b.chars()) {
-        let va = char_value(ca);
-        let vb = char_value(cb);
+        // cards are validated when the line is parsed
+        let va = char_value(ca).unwrap_or(0);
+        let vb = char_value(cb).unwrap_or(0);
         if va > vb {
             return Ordering::Greater;
         }
@@ -115,30 +132,29 @@ fn compare_hand_text(a: &str, b: &str) -> Ordering {
     Ordering::Equal
 }
 
-fn char_value(c: char) -> usize {
+fn char_value(c: char) -> Option<usize> {
     if let Some(d) = c.to_digit(10) {
-        return d as usize;
+        return Some(d as usize);
     }
     match c {
-        'T' => 10,
-        'J' => 1,
-        'Q' => 12,
-        'K' => 13,
-        'A' => 14,
-        _ => panic!("Wrong char [{:?}]", c),
+        'T' => Some(10),
+        'J' => Some(1),
+        'Q' => Some(12),
+        'K' => Some(13),
+        'A' => Some(14),
+        _ => None,
     }
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut bets: Vec<Bet> = vec![];
-    for line in lines {
-        if line.len() == 0 {
-            continue;
-        }
-        bets.push(line_to_bet(&line)?);
-    }
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    total_winnings(&parse_plays(lines)?)
+}
+
+// jokers stand in for whichever card makes the best hand
+pub fn total_winnings(plays: &[Play]) -> Result<usize> {
+    let mut bets: Vec<Bet> = plays.iter().map(play_to_bet).collect::<Result<_>>()?;
     ////println!("Bets before [{:?}]", bets);
-    bets.sort_by(|a, b| a.cmp(b));
+    bets.sort();
     ////println!("Bets after [{:?}]", bets);
     let result = bets
         .iter()
@@ -150,22 +166,18 @@ pub fn process_lines(lines: Vec<String>) -> Result<usize> {
     Ok(result)
 }
 
-fn line_to_bet(line: &str) -> Result<Bet> {
-    ////println!("Parsing line [{}]", line);
-    let mut parts = line.split(" ").into_iter();
-    //////println!("Parts [{:?}]", parts);
-    let hand_text = parts.next().unwrap();
-    let bid = parts.next().unwrap().parse::<usize>()?;
+fn play_to_bet(play: &Play) -> Result<Bet> {
+    let hand_text = play.hand;
     let hand_value = text_to_hand(hand_text)?;
-    let js = count_js(&hand_text);
+    let js = count_js(hand_text);
     let hand_value = match js {
         _ if js == 5 => Hand::FiveOfAKind,
         j if js < 5 => hand_value.hand_type.augment(j)?,
-        _ => bail!("Error line_to_bet [{}]", line),
+        _ => bail!("Error play_to_bet [{}]", hand_text),
     };
     Ok(Bet {
         hand: HandValue::new(hand_text, hand_value),
-        bid,
+        bid: play.bid,
     })
 }
 
@@ -179,7 +191,7 @@ fn text_to_hand(s: &str) -> Result<HandValue> {
     for c in text.chars() {
         *map.entry(c).or_insert(0) += 1;
     }
-    let values: Vec<usize> = map.values().map(|a| *a).collect();
+    let values: Vec<usize> = map.values().copied().collect();
     ////println!("Text map for [{}], [{:?}]", text, map);
     if values.contains(&5) {
         return Ok(HandValue::new(text, Hand::FiveOfAKind));
@@ -212,7 +224,7 @@ fn text_to_hand(s: &str) -> Result<HandValue> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
+    use crate::input::Input;
 
     #[test]
     fn test_simple_input() {
@@ -222,8 +234,9 @@ KK677 28
 KTJJT 220
 QQQJA 483
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(5905, result.unwrap());
     }
 
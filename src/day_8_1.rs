@@ -1,13 +1,18 @@
+use crate::answer::Answer;
+use crate::day_8_2;
+use crate::error::{AocError, ParseContext};
+use crate::input::Input;
+use crate::solution::Solution;
 use std::collections::HashMap;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use regex::Regex;
 
-#[derive(Clone)]
-struct Node {
-    value: String,
-    left: String,
-    right: String,
+#[derive(Clone, Debug)]
+pub struct Node {
+    pub value: String,
+    pub left: String,
+    pub right: String,
 }
 
 impl Node {
@@ -20,25 +25,63 @@ impl Node {
     }
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut line_it = lines.into_iter();
-    let step = line_it.next().unwrap();
+// the L/R steps to repeat and every node by its name
+pub struct Network<'a> {
+    pub steps: &'a str,
+    pub nodes: HashMap<String, Node>,
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: usize = 8;
+    type Parsed<'a> = Network<'a>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_network(&input.lines())
+    }
+
+    fn part_one(network: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(count_steps(network)?.into())
+    }
+
+    fn part_two(network: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_8_2::count_ghost_steps(network)?.into())
+    }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    count_steps(&parse_network(lines)?)
+}
+
+pub fn parse_network<'a>(lines: &[&'a str]) -> Result<Network<'a>> {
+    let steps = lines.first().ok_or(AocError::EmptyInput)?;
+    if steps.is_empty() {
+        bail!(AocError::Parse {
+            line: 1,
+            column: 1,
+            message: "Expected a line of L/R steps".to_string(),
+        });
+    }
     let mut map: HashMap<String, Node> = HashMap::new();
-    loop {
-        match line_it.next() {
-            None => break,
-            Some(l) if l.len() == 0 => continue,
-            Some(l) => {
-                let r = parse_line(&l)?;
+    for (i, l) in lines.iter().enumerate().skip(1) {
+        match l {
+            &"" => continue,
+            l => {
+                let r = parse_line(l).at_line(i)?;
                 map.insert(r.0.to_string(), Node::from_tuple(r));
             }
         }
     }
+    Ok(Network { steps, nodes: map })
+}
 
-    let mut current = map.get("AAA").unwrap().clone();
+fn count_steps(network: &Network) -> Result<usize> {
+    let (step, map) = (network.steps, &network.nodes);
+    let mut current = find_node(map, "AAA")?.clone();
     let mut result = 0;
     while current.value != "ZZZ" {
-        let (end_node, step_count) = navigate(&step, &current, &map)?;
+        let (end_node, step_count) = navigate(step, &current, map)?;
         result += step_count;
         current = end_node;
     }
@@ -54,8 +97,8 @@ fn navigate(steps: &str, start: &Node, map: &HashMap<String, Node>) -> Result<(N
         step_count += 1;
 
         current = match step {
-            'L' => map.get(&current.left).unwrap(),
-            'R' => map.get(&current.right).unwrap(),
+            'L' => find_node(map, &current.left)?,
+            'R' => find_node(map, &current.right)?,
             _ => bail!("Wrong step direction"),
         };
     }
@@ -64,17 +107,21 @@ fn navigate(steps: &str, start: &Node, map: &HashMap<String, Node>) -> Result<(N
 }
 
 fn parse_line(line: &str) -> Result<(String, String, String)> {
-    let re = Regex::new(r"^([A-Z]{3}) = \(([A-Z]{3}), ([A-Z]{3})\)")?;
-    for (_, [a, b, c]) in re.captures_iter(line).map(|a| a.extract()) {
+    let re = Regex::new(r"^([0-9A-Z]{3}) = \(([0-9A-Z]{3}), ([0-9A-Z]{3})\)")?;
+    if let Some((_, [a, b, c])) = re.captures_iter(line).map(|a| a.extract()).next() {
         return Ok((a.to_string(), b.to_string(), c.to_string()));
     }
-    bail!("Something is not right");
+    bail!(AocError::parse(1, "Expected `AAA = (BBB, CCC)`"));
+}
+
+pub fn find_node<'a>(map: &'a HashMap<String, Node>, key: &str) -> Result<&'a Node> {
+    map.get(key)
+        .ok_or_else(|| anyhow!("Node [{}] is not in the map", key))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn test_simple_input() {
@@ -84,8 +131,9 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(6, result.unwrap());
     }
 
@@ -96,6 +144,10 @@ ZZZ = (ZZZ, ZZZ)
         let expect = ("AAA".to_string(), "BBB".to_string(), "CCC".to_string());
         assert_eq!(expect, result);
 
+        let text = "11A = (11B, XXX)";
+        let result = parse_line(text)?;
+        let expect = ("11A".to_string(), "11B".to_string(), "XXX".to_string());
+        assert_eq!(expect, result);
         Ok(())
     }
 }
@@ -1,61 +1,24 @@
 use std::collections::HashMap;
 
+use crate::day_8_1::{find_node, parse_network, Network, Node};
+
 use anyhow::{bail, Result};
 use num::integer::lcm;
-use rayon;
 use rayon::prelude::*;
-use regex::Regex;
-
-#[derive(Clone, Debug)]
-struct Node {
-    value: String,
-    left: String,
-    right: String,
-}
 
-impl Node {
-    fn from_tuple(t: (String, String, String)) -> Self {
-        Node {
-            value: t.0,
-            left: t.1,
-            right: t.2,
-        }
-    }
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    count_ghost_steps(&parse_network(lines)?)
 }
 
-pub fn process_lines(lines: Vec<String>) -> Result<usize> {
-    let mut line_it = lines.into_iter();
-    let step = line_it.next().unwrap();
-    let mut map: HashMap<String, Node> = HashMap::new();
-    let mut starter_nodes: Vec<String> = vec![];
-    loop {
-        match line_it.next() {
-            None => break,
-            Some(l) if l.len() == 0 => continue,
-            Some(l) => {
-                let r = parse_line(&l)?;
-                let key = r.0.to_string();
-                map.insert(key.clone(), Node::from_tuple(r));
-                if key.ends_with("A") {
-                    starter_nodes.push(key);
-                }
-            }
-        }
-    }
-
-    let starters: &Vec<&Node> = &starter_nodes
-        .iter()
-        .map(|k| map.get(k))
-        .map(|o| o.unwrap())
-        .collect();
+// every node ending in A walks at once until all of them end in Z
+pub fn count_ghost_steps(network: &Network) -> Result<usize> {
+    let (step, map) = (network.steps, &network.nodes);
+    let starters: &Vec<&Node> = &map.values().filter(|n| n.value.ends_with("A")).collect();
     let finals: Vec<(Node, usize)> = starters
         .par_iter()
-        .map(|n| navigate_one(&step, n, &map).unwrap())
-        .collect();
-    let result = finals
-        .iter()
-        .map(|p| p.1)
-        .fold(1 as usize, |acc, e| lcm(acc, e));
+        .map(|n| navigate_one(step, n, map))
+        .collect::<Result<_>>()?;
+    let result = finals.iter().map(|p| p.1).fold(1_usize, lcm);
     Ok(result)
 }
 
@@ -67,13 +30,13 @@ fn navigate_one(steps: &str, start: &Node, map: &HashMap<String, Node>) -> Resul
         if current.value.ends_with("Z") {
             break;
         }
-        let step = steps.chars().nth(pos % steps.len()).unwrap();
+        let step = steps.as_bytes()[pos % steps.len()] as char;
         step_count += 1;
         pos += 1;
 
         current = match step {
-            'L' => map.get(&current.left).unwrap(),
-            'R' => map.get(&current.right).unwrap(),
+            'L' => find_node(map, &current.left)?,
+            'R' => find_node(map, &current.right)?,
             _ => bail!("Wrong step direction"),
         };
     }
@@ -81,18 +44,10 @@ fn navigate_one(steps: &str, start: &Node, map: &HashMap<String, Node>) -> Resul
     Ok((current.clone(), step_count))
 }
 
-fn parse_line(line: &str) -> Result<(String, String, String)> {
-    let re = Regex::new(r"^([0-9A-Z]{3}) = \(([0-9A-Z]{3}), ([0-9A-Z]{3})\)")?;
-    for (_, [a, b, c]) in re.captures_iter(line).map(|a| a.extract()) {
-        return Ok((a.to_string(), b.to_string(), c.to_string()));
-    }
-    bail!("Something is not right");
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
+    use crate::input::Input;
 
     #[test]
     fn test_simple_input() {
@@ -107,22 +62,9 @@ mod tests {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)
 ";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(6, result.unwrap());
     }
-
-    #[test]
-    fn test_parse_line() -> Result<()> {
-        let text = "AAA = (BBB, CCC)";
-        let result = parse_line(text)?;
-        let expect = ("AAA".to_string(), "BBB".to_string(), "CCC".to_string());
-        assert_eq!(expect, result);
-
-        let text = "11A = (11B, XXX)";
-        let result = parse_line(text)?;
-        let expect = ("11A".to_string(), "11B".to_string(), "XXX".to_string());
-        assert_eq!(expect, result);
-        Ok(())
-    }
 }
@@ -1,34 +1,75 @@
+use crate::answer::Answer;
+use crate::day_9_2;
+use crate::error::{AocError, ParseContext};
+use crate::input::Input;
 use crate::re_utils;
+use crate::solution::Solution;
 use anyhow::Result;
 //use std::cmp::{min,max};
 
 type Number = i64;
-type Vuz = Vec<Number>;
-
-pub fn process_lines(lines: Vec<String>) -> Result<Number> {
-    let result = lines
-        .iter()
-        .filter(|l| l.len() > 0)
-        .map(|l| process_line(l))
-        .map(|r| match r {
-            Ok(r) => r,
-            _ => 0,
-        })
-        .sum::<Number>();
+pub type Vuz = Vec<Number>;
 
-    Ok(result)
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: usize = 9;
+    type Parsed<'a> = Vec<Vuz>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        parse_histories(&input.lines())
+    }
+
+    fn part_one(histories: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(sum_next_values(histories)?.into())
+    }
+
+    fn part_two(histories: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(day_9_2::sum_previous_values(histories)?.into())
+    }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<Number> {
+    sum_next_values(&parse_histories(lines)?)
+}
+
+// the numbers of every line, blank lines are skipped
+pub fn parse_histories(lines: &[&str]) -> Result<Vec<Vuz>> {
+    let mut histories = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        histories.push(parse_history(line).at_line(i)?);
+    }
+    Ok(histories)
 }
 
-fn process_line(line: &str) -> Result<Number> {
+fn parse_history(line: &str) -> Result<Vuz> {
     let nums = re_utils::parse_line_numbers_i64(line)?;
-    let res = process_nums(&nums)?;
-    Ok(diff_to_augment(*nums.last().unwrap(), res))
+    if nums.is_empty() {
+        return Err(AocError::parse(1, "Expected a sequence of numbers").into());
+    }
+    Ok(nums)
+}
+
+fn sum_next_values(histories: &[Vuz]) -> Result<Number> {
+    let mut result = 0;
+    for nums in histories {
+        result += next_value(nums)?;
+    }
+    Ok(result)
+}
+
+fn next_value(nums: &Vuz) -> Result<Number> {
+    let res = process_nums(nums)?;
+    Ok(diff_to_augment(nums.last().copied().unwrap_or(0), res))
 }
 
 fn process_nums(nums: &Vuz) -> Result<Number> {
     //println!("process_nums input [{:+?}]", nums);
     let all_zeroes = nums.iter().all(|a| *a == 0);
-    if all_zeroes || nums.len() == 0 {
+    if all_zeroes || nums.is_empty() {
         return Ok(0);
     }
     let mut reduced: Vuz = vec![];
@@ -39,7 +80,8 @@ fn process_nums(nums: &Vuz) -> Result<Number> {
     }
 
     let augment_value = process_nums(&reduced)?;
-    let result = diff_to_augment(*reduced.last().unwrap(), augment_value);
+    // a single number has nothing to reduce, its difference is zero
+    let result = diff_to_augment(reduced.last().copied().unwrap_or(0), augment_value);
     //println!("process_nums result [{}]", result);
 
     Ok(result)
@@ -56,28 +98,27 @@ fn diff(a: Number, b: Number) -> Number {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn test_simple_input() {
         let input = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(114, result.unwrap());
     }
 
     #[test]
-    fn test_process_line() {
-        let line = "10 13 16 21 30 45";
-        let result = process_line(line);
-        //println!("Result test [{:?}]", result);
-        assert_eq!(68, result.unwrap());
-
-        let line = "4 11 32 93 230 496 975 1798 3150 5243 8219 11979 16093 20384 27760 52853 143831 431834 1235397 3270284 8055403";
-        let result = process_line(line);
-        //println!("Result test [{:?}]", result);
-        assert_eq!(18673859, result.unwrap());
+    fn test_next_value() -> Result<()> {
+        let nums = parse_history("10 13 16 21 30 45")?;
+        assert_eq!(68, next_value(&nums)?);
+
+        let nums = parse_history("4 11 32 93 230 496 975 1798 3150 5243 8219 11979 16093 20384 27760 52853 143831 431834 1235397 3270284 8055403")?;
+        assert_eq!(18673859, next_value(&nums)?);
+
+        assert!(parse_history("no numbers").is_err());
+        Ok(())
     }
 }
@@ -1,33 +1,29 @@
-use crate::re_utils;
+use crate::day_9_1::{parse_histories, Vuz};
 use anyhow::Result;
 
 type Number = i64;
-type Vuz = Vec<Number>;
 
-pub fn process_lines(lines: Vec<String>) -> Result<Number> {
-    let result = lines
-        .iter()
-        .filter(|l| l.len() > 0)
-        .map(|l| process_line(l))
-        .map(|r| match r {
-            Ok(r) => r,
-            _ => 0,
-        })
-        .sum::<Number>();
+pub fn process_lines(lines: &[&str]) -> Result<Number> {
+    sum_previous_values(&parse_histories(lines)?)
+}
 
+pub fn sum_previous_values(histories: &[Vuz]) -> Result<Number> {
+    let mut result = 0;
+    for nums in histories {
+        result += previous_value(nums)?;
+    }
     Ok(result)
 }
 
-fn process_line(line: &str) -> Result<Number> {
-    let nums = re_utils::parse_line_numbers_i64(line)?;
-    let res = process_nums(&nums)?;
-    Ok(diff_to_augment(*nums.first().unwrap(), res))
+fn previous_value(nums: &Vuz) -> Result<Number> {
+    let res = process_nums(nums)?;
+    Ok(diff_to_augment(nums.first().copied().unwrap_or(0), res))
 }
 
 fn process_nums(nums: &Vuz) -> Result<Number> {
     //println!("process_nums input [{:+?}]", nums);
     let all_zeroes = nums.iter().all(|a| *a == 0);
-    if all_zeroes || nums.len() == 0 {
+    if all_zeroes || nums.is_empty() {
         return Ok(0);
     }
     let mut reduced: Vuz = vec![];
@@ -38,7 +34,8 @@ fn process_nums(nums: &Vuz) -> Result<Number> {
     }
 
     let augment_value = process_nums(&reduced)?;
-    let result = diff_to_augment(*reduced.first().unwrap(), augment_value);
+    // a single number has nothing to reduce, its difference is zero
+    let result = diff_to_augment(reduced.first().copied().unwrap_or(0), augment_value);
     //println!("process_nums result [{}]", result);
 
     Ok(result)
@@ -55,23 +52,22 @@ fn diff(a: Number, b: Number) -> Number {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
+    use crate::input::Input;
 
     #[test]
     fn test_simple_input() {
         let input = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
-        let lines = utils::string_to_lines(input.to_string());
-        let result = process_lines(lines);
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
         assert_eq!(2, result.unwrap());
     }
 
     #[test]
-    fn test_process_line() {
-        let line = "10 13 16 21 30 45";
-        let result = process_line(line);
-        //println!("Result test [{:?}]", result);
+    fn test_previous_value() {
+        let result = previous_value(&vec![10, 13, 16, 21, 30, 45]);
         assert_eq!(5, result.unwrap());
     }
 }
@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+
+#[derive(Debug, PartialEq)]
+pub enum AocError {
+    UnknownDay(usize),
+    UnknownPart {
+        day: usize,
+        part: usize,
+    },
+    // line and column are 1-based, a line of 0 means it is not known yet
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    EmptyInput,
+}
+
+impl AocError {
+    pub fn parse(column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: 0,
+            column,
+            message: message.into(),
+        }
+    }
+
+    // places a parse error at the given 0-based line index
+    pub fn at_line(self, index: usize) -> Self {
+        match self {
+            AocError::Parse {
+                line: 0,
+                column,
+                message,
+            } => AocError::Parse {
+                line: index + 1,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+// 1-based column where `part` starts, `part` must be a slice of `line`
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line[..offset.min(line.len())].chars().count() + 1
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::UnknownDay(day) => write!(f, "Day {} is not implemented", day),
+            AocError::UnknownPart { day, part } => {
+                write!(f, "Day {} has no part {}", day, part)
+            }
+            AocError::Parse {
+                line: 0,
+                column,
+                message,
+            } => write!(f, "Parse error at column {}: {}", column, message),
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Parse error at line {}, column {}: {}",
+                line, column, message
+            ),
+            AocError::EmptyInput => write!(f, "Input is empty"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+pub trait ParseContext<T> {
+    // places the error at the given 0-based line index; errors that are not
+    // parse errors yet become one pointing to the start of the line
+    fn at_line(self, index: usize) -> Result<T>;
+}
+
+impl<T> ParseContext<T> for Result<T> {
+    fn at_line(self, index: usize) -> Result<T> {
+        self.map_err(|e| match e.downcast::<AocError>() {
+            Ok(e) => e.at_line(index).into(),
+            Err(e) => AocError::Parse {
+                line: index + 1,
+                column: 1,
+                message: e.to_string(),
+            }
+            .into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+
+    use super::*;
+
+    #[test]
+    fn test_column_of() {
+        let line = "Game 1: 3 blue";
+        let part = line.split(':').nth(1).unwrap();
+        assert_eq!(8, column_of(line, part));
+        assert_eq!(1, column_of(line, line));
+    }
+
+    #[test]
+    fn test_at_line_sets_line_of_parse_error() {
+        let result: Result<()> = Err(AocError::parse(4, "bad digit").into());
+        let err = result.at_line(2).unwrap_err();
+        assert_eq!(
+            "Parse error at line 3, column 4: bad digit",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_at_line_wraps_other_errors() {
+        let result: Result<()> = (|| bail!("no separator"))();
+        let err = result.at_line(0).unwrap_err();
+        assert_eq!(
+            Some(&AocError::Parse {
+                line: 1,
+                column: 1,
+                message: "no separator".to_string()
+            }),
+            err.downcast_ref::<AocError>()
+        );
+    }
+
+    #[test]
+    fn test_at_line_keeps_other_variants() {
+        let result: Result<()> = Err(AocError::EmptyInput.into());
+        let err = result.at_line(7).unwrap_err();
+        assert_eq!(Some(&AocError::EmptyInput), err.downcast_ref::<AocError>());
+    }
+}
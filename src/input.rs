@@ -0,0 +1,246 @@
+use std::fmt::Display;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+const STDIN_PATH: &str = "<stdin>";
+
+// Why an input could not be read, always with the path it was read from
+#[derive(Debug, PartialEq)]
+pub enum InputError {
+    NotFound(PathBuf),
+    Empty(PathBuf),
+    // line is 1-based, the first line with bytes that are not UTF-8
+    InvalidUtf8 { path: PathBuf, line: usize },
+    Io { path: PathBuf, message: String },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(path) => write!(f, "Input [{}] not found", path.display()),
+            InputError::Empty(path) => write!(f, "Input [{}] is empty", path.display()),
+            InputError::InvalidUtf8 { path, line } => write!(
+                f,
+                "Input [{}] is not valid UTF-8 at line {}",
+                path.display(),
+                line
+            ),
+            InputError::Io { path, message } => {
+                write!(f, "Could not read input [{}]: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// The whole puzzle input read into a single buffer, lines and blocks are
+// borrowed from it instead of being copied one String at a time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Input, InputError> {
+        let path = path.as_ref();
+        match std::fs::read(path) {
+            Ok(bytes) => Input::from_bytes(bytes, path),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(InputError::NotFound(path.to_path_buf()))
+            }
+            Err(e) => Err(InputError::Io {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    pub fn from_reader(mut reader: impl Read) -> Result<Input, InputError> {
+        let mut bytes: Vec<u8> = vec![];
+        reader.read_to_end(&mut bytes).map_err(|e| InputError::Io {
+            path: PathBuf::from(STDIN_PATH),
+            message: e.to_string(),
+        })?;
+        Input::from_bytes(bytes, Path::new(STDIN_PATH))
+    }
+
+    pub fn from_stdin() -> Result<Input, InputError> {
+        Input::from_reader(std::io::stdin().lock())
+    }
+
+    // blank inputs are rejected here so a missing download is not solved as
+    // an empty puzzle
+    fn from_bytes(bytes: Vec<u8>, path: &Path) -> Result<Input, InputError> {
+        let text = String::from_utf8(bytes).map_err(|e| {
+            let valid = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+            InputError::InvalidUtf8 {
+                path: path.to_path_buf(),
+                line: valid.iter().filter(|b| **b == b'\n').count() + 1,
+            }
+        })?;
+        let input = Input::from(text);
+        if input.is_blank() {
+            return Err(InputError::Empty(path.to_path_buf()));
+        }
+        Ok(input)
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    // every line, blank ones included
+    pub fn lines(&self) -> Vec<&str> {
+        self.text.lines().collect()
+    }
+
+    // groups of lines separated by one or more blank lines
+    pub fn blocks(&self) -> Blocks<'_> {
+        Blocks {
+            lines: self.text.lines(),
+        }
+    }
+
+    // 0-based index of a line borrowed from this input, so blocks can place
+    // their errors in the whole input
+    pub fn line_index(&self, line: &str) -> usize {
+        let offset = (line.as_ptr() as usize).saturating_sub(self.text.as_ptr() as usize);
+        self.text[..offset.min(self.text.len())]
+            .matches('\n')
+            .count()
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+}
+
+// CRLF line endings are turned into LF so `text()` and `lines()` agree
+fn normalize_line_endings(text: String) -> String {
+    if text.contains('\r') {
+        text.replace("\r\n", "\n")
+    } else {
+        text
+    }
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        Input::from(value.to_string())
+    }
+}
+
+impl From<String> for Input {
+    fn from(value: String) -> Self {
+        Input {
+            text: normalize_line_endings(value),
+        }
+    }
+}
+
+pub struct Blocks<'a> {
+    lines: std::str::Lines<'a>,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block: Vec<&'a str> = vec![];
+        for line in self.lines.by_ref() {
+            if !line.is_empty() {
+                block.push(line);
+            } else if !block.is_empty() {
+                break;
+            }
+        }
+        if block.is_empty() {
+            None
+        } else {
+            Some(block)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#.#
+..#
+
+#..
+
+
+.##
+";
+
+    #[test]
+    fn test_lines_keep_blank_lines() {
+        let input = Input::from(TEXT);
+        assert_eq!(vec!["#.#", "..#", "", "#..", "", "", ".##"], input.lines());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = Input::from(TEXT);
+        let blocks: Vec<Vec<&str>> = input.blocks().collect();
+        assert_eq!(vec![vec!["#.#", "..#"], vec!["#.."], vec![".##"]], blocks);
+        assert_eq!(0, Input::from("\n\n").blocks().count());
+        let indexes: Vec<usize> = blocks.iter().map(|b| input.line_index(b[0])).collect();
+        assert_eq!(vec![0, 3, 6], indexes);
+    }
+
+    #[test]
+    fn test_from_reader() -> Result<()> {
+        let input = Input::from_reader(TEXT.as_bytes())?;
+        assert_eq!(TEXT, input.text());
+        assert!(!input.is_blank());
+        assert!(Input::from(" \n\n").is_blank());
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf() {
+        let input = Input::from("#.#\r\n..#\r\n\r\n#..\r\n");
+        assert_eq!("#.#\n..#\n\n#..\n", input.text());
+        assert_eq!(vec!["#.#", "..#", "", "#.."], input.lines());
+        assert_eq!(2, input.blocks().count());
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let dir = std::env::temp_dir().join(format!("aoc_input_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let missing = dir.join("missing.txt");
+        assert_eq!(
+            Err(InputError::NotFound(missing.clone())),
+            Input::from_file(&missing)
+        );
+
+        let empty = dir.join("empty.txt");
+        std::fs::write(&empty, "\n\n").unwrap();
+        assert_eq!(
+            Err(InputError::Empty(empty.clone())),
+            Input::from_file(&empty)
+        );
+
+        let invalid = dir.join("invalid.txt");
+        std::fs::write(&invalid, b"abc\ndef\n\xff\n").unwrap();
+        assert_eq!(
+            Err(InputError::InvalidUtf8 {
+                path: invalid.clone(),
+                line: 3
+            }),
+            Input::from_file(&invalid)
+        );
+
+        let valid = dir.join("valid.txt");
+        std::fs::write(&valid, "abc\r\n").unwrap();
+        assert_eq!(Ok(Input::from("abc\n")), Input::from_file(&valid));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
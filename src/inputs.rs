@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::answers::Answers;
+
+// Puzzle inputs live in `inputs/day_NN.txt`, examples in
+// `examples/day_NN_M.txt` next to an optional `examples/day_NN_M.toml` with
+// the answers expected for them, in the same format as the answers file
+pub const EXAMPLES_DIR: &str = "examples";
+
+#[derive(Debug)]
+pub struct Example {
+    pub day: usize,
+    pub number: usize,
+    pub path: PathBuf,
+    pub expected: Answers,
+}
+
+// every example of the day found in `dir`, in example number order
+pub fn examples(dir: &Path, day: usize) -> Result<Vec<Example>> {
+    let prefix = format!("day_{:02}_", day);
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read examples dir [{}]", dir.display()))?;
+    let mut result: Vec<Example> = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let number = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|name| name.strip_suffix(".txt"))
+            .and_then(|number| number.parse::<usize>().ok());
+        let Some(number) = number else {
+            continue;
+        };
+        let answers_path = path.with_extension("toml");
+        let expected = if answers_path.exists() {
+            Answers::load(&answers_path.to_string_lossy())?
+        } else {
+            Answers::default()
+        };
+        result.push(Example {
+            day,
+            number,
+            path,
+            expected,
+        });
+    }
+    result.sort_by_key(|example| example.number);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+
+    #[test]
+    fn test_examples() -> Result<()> {
+        let examples = examples(Path::new(EXAMPLES_DIR), 1)?;
+        let numbers: Vec<usize> = examples.iter().map(|e| e.number).collect();
+        assert_eq!(vec![1, 2], numbers);
+        assert_eq!(Some(&Answer::from(142_u64)), examples[0].expected.get(1, 1));
+        assert_eq!(None, examples[0].expected.get(1, 2));
+        assert_eq!(Some(&Answer::from(281_u64)), examples[1].expected.get(1, 2));
+        assert!(super::examples(Path::new(EXAMPLES_DIR), 25)?.is_empty());
+        Ok(())
+    }
+}
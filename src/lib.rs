@@ -1,9 +1,16 @@
 #![allow(unused)]
-use anyhow::Result;
+use anyhow::{bail, Result};
 
+pub use crate::answer::Answer;
+pub use crate::answers::Answers;
+pub use crate::error::AocError;
+pub use crate::input::{Input, InputError};
 use crate::prelude::*;
+pub use crate::solution::{DayPart, Solution};
 
-mod col_utils;
+mod answer;
+mod answers;
+pub mod cycle;
 mod day_10_1;
 mod day_11_1;
 mod day_11_2;
@@ -32,21 +39,61 @@ mod day_8_1;
 mod day_8_2;
 mod day_9_1;
 mod day_9_2;
+mod error;
+mod input;
+pub mod inputs;
+pub mod polygon;
 pub mod prelude;
+pub mod ranges;
 mod re_utils;
+pub mod registry;
+pub mod render;
+pub mod search;
+mod solution;
+pub mod timing;
 mod trebuchet;
 mod trebuchet_2;
-mod utils;
-
-pub fn process_lines(lines: Vec<String>, day: usize, day_part: usize) -> Result<usize> {
-    match day {
-        13 => day_13_1::process(lines, day_part),
-        14 => day_14::process(lines, day_part),
-        15 => day_15::process(lines, day_part),
-        16 => day_16::process(lines, day_part),
-        17 => day_17::process(&lines, day_part),
-        18 => day_18::process(&lines, day_part),
-        19 => day_19::process(&lines, day_part),
-        _ => panic!("Not implemented {:?}", day),
+
+pub fn days() -> Vec<usize> {
+    registry::DAYS.iter().map(|d| d.day).collect()
+}
+
+pub fn process_lines(input: &Input, day: usize, day_part: usize) -> Result<Answer> {
+    let (entry, part) = find_day_part(day, day_part)?;
+    entry.run(input, part)
+}
+
+// the drawing a day makes of a part, None when the day has none
+pub fn render_day(input: &Input, day: usize, day_part: usize) -> Result<Option<Grid<char>>> {
+    let (entry, part) = find_day_part(day, day_part)?;
+    entry.render(input, part)
+}
+
+fn find_day_part(day: usize, day_part: usize) -> Result<(&'static registry::Day, DayPart)> {
+    let entry = registry::find(day).ok_or(AocError::UnknownDay(day))?;
+    let part = DayPart::try_from(day_part).map_err(|_| AocError::UnknownPart {
+        day,
+        part: day_part,
+    })?;
+    Ok((entry, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_day() {
+        let err = process_lines(&Input::from("1"), 25, 1).unwrap_err();
+        assert_eq!(Some(&AocError::UnknownDay(25)), err.downcast_ref());
+    }
+
+    #[test]
+    fn test_unknown_part() {
+        let err = process_lines(&Input::from("1"), 1, 3).unwrap_err();
+        assert_eq!(
+            Some(&AocError::UnknownPart { day: 1, part: 3 }),
+            err.downcast_ref()
+        );
     }
 }
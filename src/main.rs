@@ -1,13 +1,130 @@
 #![allow(unused)]
 
+use std::time::Instant;
+
+use anyhow::Result;
+
 use advent_of_code::prelude::*;
-mod utils;
+mod bench;
+mod cli;
+mod verify;
+
+use advent_of_code::render::palette;
+use advent_of_code::{days, process_lines, render_day, Input, InputError};
+use cli::{Args, InputSource};
 
-use advent_of_code::process_lines;
-use utils::read_lines;
+// pixels per cell side in the saved drawings
+const PPM_SCALE: usize = 4;
 
 fn main() {
-    let lines = read_lines("input.txt".to_string());
-    let result = process_lines(lines, 19, 1);
-    println!("Result [{:?}]", result);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match cli::parse_args(&args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let is_ok = match args.bench {
+        Some(runs) => bench::bench(&args, runs),
+        None if args.verify => verify::verify(&args),
+        None if args.examples => verify::verify_examples(&args),
+        None => run(&args),
+    };
+    if !is_ok {
+        std::process::exit(1);
+    }
+}
+
+// returns false when any of the selected days failed
+fn run(args: &Args) -> bool {
+    let selected_days = args.days.days(&days());
+    if selected_days.is_empty() {
+        eprintln!("No implemented day matches the selection");
+        return false;
+    }
+    if selected_days.len() > 1 && args.input == InputSource::Stdin {
+        eprintln!("Reading from stdin is only supported for a single day");
+        return false;
+    }
+    let mut is_ok = true;
+    for day in selected_days {
+        let input = match read_day_input(&args.input, day) {
+            Ok(input) => input,
+            Err(e) => {
+                is_ok = false;
+                println!("Day {:>2}: error {}", day, describe_input_error(&e));
+                continue;
+            }
+        };
+        for part in &args.parts {
+            let start = Instant::now();
+            let result = process_lines(&input, day, *part);
+            let elapsed = start.elapsed();
+            match result {
+                Ok(answer) => {
+                    println!("Day {:>2} part {}: {} ({:.2?})", day, part, answer, elapsed)
+                }
+                Err(e) => {
+                    is_ok = false;
+                    println!(
+                        "Day {:>2} part {}: error {:#} ({:.2?})",
+                        day, part, e, elapsed
+                    )
+                }
+            }
+            if args.render || args.ppm.is_some() {
+                is_ok &= draw(args, &input, day, *part);
+            }
+        }
+    }
+    is_ok
+}
+
+// prints or saves the drawing of a part, false when it could not be made
+fn draw(args: &Args, input: &Input, day: usize, part: usize) -> bool {
+    let grid = match render_day(input, day, part) {
+        Ok(Some(grid)) => grid,
+        Ok(None) => {
+            println!("Day {:>2} part {}: nothing to draw", day, part);
+            return true;
+        }
+        Err(e) => {
+            println!("Day {:>2} part {}: error drawing {:#}", day, part, e);
+            return false;
+        }
+    };
+    if args.render {
+        println!("{}", grid);
+    }
+    if let Some(template) = &args.ppm {
+        let path = cli::image_path(template, day, part);
+        if let Err(e) = grid.save_ppm(&path, PPM_SCALE, |c| palette(*c)) {
+            println!("Day {:>2} part {}: error {:#}", day, part, e);
+            return false;
+        }
+        println!("Day {:>2} part {}: drawing saved to [{}]", day, part, path);
+    }
+    true
+}
+
+fn read_day_input(input: &InputSource, day: usize) -> Result<Input, InputError> {
+    match input.for_day(day) {
+        InputSource::Stdin => Input::from_stdin(),
+        InputSource::File(path) => Input::from_file(path),
+    }
+}
+
+// the read error plus what to do about it
+fn describe_input_error(e: &InputError) -> String {
+    match e {
+        InputError::NotFound(_) => format!("{}, download it or pass --input <FILE>", e),
+        InputError::Empty(_) => format!("{}, the download may have failed", e),
+        InputError::InvalidUtf8 { .. } => format!("{}, save it as UTF-8 text", e),
+        InputError::Io { .. } => e.to_string(),
+    }
 }
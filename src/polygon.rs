@@ -0,0 +1,77 @@
+use num::Integer;
+
+use crate::prelude::{Direction, Point};
+
+// Corners of the closed polygon walked by the moves, starting at the origin.
+// The walk is expected to end where it started.
+pub fn from_moves<'a>(moves: impl IntoIterator<Item = &'a (Direction, usize)>) -> Vec<Point> {
+    let mut position = Point::default();
+    let mut vertices = vec![position];
+    for (direction, len) in moves {
+        position = position + direction.delta() * *len as i64;
+        vertices.push(position);
+    }
+    if vertices.len() > 1 && vertices.last() == vertices.first() {
+        vertices.pop();
+    }
+    vertices
+}
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (&Point, &Point)> {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+}
+
+// twice the area, shoelace formula, so it stays an integer
+pub fn double_area(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>()
+        .abs()
+}
+
+// lattice points on the edges, corners included once
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| (b.x - a.x).abs().gcd(&(b.y - a.y).abs()))
+        .sum()
+}
+
+// lattice points strictly inside, from Pick's theorem A = I + B / 2 - 1
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+// lattice points inside or on the edges, the tiles a dug out loop covers
+pub fn lattice_points(vertices: &[Point]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square() {
+        // 3x3 tiles walked along their centres
+        let moves = [
+            (Direction::East, 2),
+            (Direction::South, 2),
+            (Direction::West, 2),
+            (Direction::North, 2),
+        ];
+        let vertices = from_moves(&moves);
+        assert_eq!(4, vertices.len());
+        assert_eq!(8, double_area(&vertices));
+        assert_eq!(8, boundary_points(&vertices));
+        assert_eq!(1, interior_points(&vertices));
+        assert_eq!(9, lattice_points(&vertices));
+    }
+
+    #[test]
+    fn test_diagonal_edges() {
+        let vertices = [Point::from(0, 0), Point::from(4, 0), Point::from(0, 4)];
+        assert_eq!(16, double_area(&vertices));
+        assert_eq!(12, boundary_points(&vertices));
+        assert_eq!(3, interior_points(&vertices));
+    }
+}
@@ -1,13 +1,17 @@
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::HashSet,
     fmt::{Debug, Display},
+    ops::{Add, Mul, Sub},
     str::FromStr,
 };
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use num::{Integer, Signed};
 
-#[derive(Clone)]
+use crate::error::{column_of, AocError};
+use crate::search;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Grid<E> {
     pub data: Vec<E>,
     pub width: usize,
@@ -30,29 +34,161 @@ impl<E> Grid<E> {
             .collect()
     }
     pub fn get_neighbors(&self, pos: &Coord) -> Vec<Coord> {
-        Direction::all_dir()
-            .iter()
-            .map(|d| d.coord_delta())
-            .map(|delta| pos.plus_delta(delta))
-            .filter(|r| r.is_ok())
-            .map(|r| r.unwrap())
-            .filter(|c| self.is_within_bounds(c))
-            .collect()
+        self.neighbors4(pos).map(|(coord, _)| coord).collect()
+    }
+
+    // the up to 4 orthogonal neighbors of `coord`, north, south, east, west
+    pub fn neighbors4(&self, coord: &Coord) -> Neighbors<'_, E> {
+        self.stencil(coord, &NEIGHBORS_4)
+    }
+
+    // the up to 8 neighbors of `coord`, diagonals included
+    pub fn neighbors8(&self, coord: &Coord) -> Neighbors<'_, E> {
+        self.stencil(coord, &NEIGHBORS_8)
+    }
+
+    // the cells at each of `deltas` from `coord`, skipping the ones off the
+    // grid unless the iterator is made `wrapping`
+    pub fn stencil<'a>(&'a self, coord: &Coord, deltas: &'a [Point]) -> Neighbors<'a, E> {
+        Neighbors {
+            grid: self,
+            center: *coord,
+            deltas: deltas.iter(),
+            is_wrapping: false,
+        }
     }
 
     pub fn is_within_bounds(&self, coord: &Coord) -> bool {
         coord.x < self.width && coord.y < self.height
     }
+
+    // the grid coord of `point`, None when it is off the grid
+    pub fn coord_of(&self, point: &Point) -> Option<Coord> {
+        point.convert().filter(|c| self.is_within_bounds(c))
+    }
+
+    pub fn get(&self, coord: &Coord) -> Option<&E> {
+        if self.is_within_bounds(coord) {
+            self.data.get(coord.to_pos(self.width))
+        } else {
+            None
+        }
+    }
 }
 
-impl<E: ToString> Grid<E> {
-    pub fn print(&self) {
-        for i in 0..self.data.len() {
-            if i % self.width == 0 {
-                println!("");
+impl<E> Grid<E> {
+    // one cell per char, `to_cell` turns a char into a cell or says why it
+    // is not one
+    pub fn from_chars_with<F>(lines: &[&str], mut to_cell: F) -> Result<Grid<E>>
+    where
+        F: FnMut(char) -> Result<E>,
+    {
+        let width = lines.first().map(|line| line.chars().count()).unwrap_or(0);
+        if width == 0 {
+            return Err(AocError::EmptyInput.into());
+        }
+        let mut data: Vec<E> = Vec::with_capacity(width * lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            check_row_width(i, line.chars().count(), width)?;
+            for (column, c) in line.chars().enumerate() {
+                let cell = to_cell(c)
+                    .map_err(|e| AocError::parse(column + 1, e.to_string()).at_line(i))?;
+                data.push(cell);
             }
-            print!("{}", &self.data[i].to_string());
         }
+        Ok(Grid {
+            data,
+            width,
+            height: lines.len(),
+        })
+    }
+}
+
+impl<E> Grid<E>
+where
+    E: TryFrom<char>,
+    <E as TryFrom<char>>::Error: Display,
+{
+    pub fn from_chars(lines: &[&str]) -> Result<Grid<E>> {
+        Grid::from_chars_with(lines, |c| E::try_from(c).map_err(|e| anyhow!("{}", e)))
+    }
+}
+
+pub const NEIGHBORS_4: [Point; 4] = [
+    Point { x: 0, y: -1 },
+    Point { x: 0, y: 1 },
+    Point { x: 1, y: 0 },
+    Point { x: -1, y: 0 },
+];
+
+pub const NEIGHBORS_8: [Point; 8] = [
+    Point { x: -1, y: -1 },
+    Point { x: 0, y: -1 },
+    Point { x: 1, y: -1 },
+    Point { x: -1, y: 0 },
+    Point { x: 1, y: 0 },
+    Point { x: -1, y: 1 },
+    Point { x: 0, y: 1 },
+    Point { x: 1, y: 1 },
+];
+
+pub struct Neighbors<'a, E> {
+    grid: &'a Grid<E>,
+    center: Coord,
+    deltas: std::slice::Iter<'a, Point>,
+    is_wrapping: bool,
+}
+
+impl<E> Neighbors<'_, E> {
+    // treats the grid as a torus, walking off one edge comes back on the
+    // opposite one
+    pub fn wrapping(mut self) -> Self {
+        self.is_wrapping = true;
+        self
+    }
+}
+
+impl<'a, E> Iterator for Neighbors<'a, E> {
+    type Item = (Coord, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (width, height) = (self.grid.width as i64, self.grid.height as i64);
+        for delta in self.deltas.by_ref() {
+            let point = Point::from(self.center.x as i64, self.center.y as i64) + *delta;
+            let coord = if self.is_wrapping && width > 0 && height > 0 {
+                Point::from(point.x.rem_euclid(width), point.y.rem_euclid(height)).convert()
+            } else {
+                self.grid.coord_of(&point)
+            };
+            if let Some(coord) = coord {
+                return Some((coord, &self.grid.data[coord.to_pos(self.grid.width)]));
+            }
+        }
+        None
+    }
+}
+
+// rows of a char grid must all be as wide as the first one
+fn check_row_width(index: usize, len: usize, expected: usize) -> Result<()> {
+    if len != expected {
+        bail!(AocError::parse(
+            expected.min(len) + 1,
+            format!("Row has {} chars, expected {}", len, expected)
+        )
+        .at_line(index));
+    }
+    Ok(())
+}
+
+impl<E: Display> Display for Grid<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.overlay())
+    }
+}
+
+impl<E: Display> Grid<E> {
+    pub fn print(&self) {
+        println!("{}", self);
     }
 }
 
@@ -65,35 +201,24 @@ impl<E: PartialEq> Grid<E> {
     }
 
     pub fn get_edge_reachable_pos_set(&self, colision_elem: &E) -> HashSet<usize> {
-        let mut edge_reachable_set: HashSet<usize> = HashSet::new();
-        let mut visited: HashSet<usize> = HashSet::new();
-        for i in 0..self.data.len() {
-            let c = Coord::from_pos(i, self.width);
-            let (x, y) = (c.x, c.y);
-            if x == 0 || y == 0 || x == self.width - 1 || y == self.height - 1 {
-                //we are at an edge so everything is reachable from here
-                if colision_elem.eq(&self.data[i]) {
-                    continue;
-                }
-                let mut q: VecDeque<usize> = VecDeque::new();
-                q.push_front(i);
-                while !q.is_empty() {
-                    let pos = q.pop_front().unwrap();
-                    if visited.contains(&pos) {
-                        continue;
-                    }
-                    visited.insert(pos);
-                    if self.data[pos] == *colision_elem {
-                        continue;
-                    }
-                    edge_reachable_set.insert(pos);
-                    for ele in self.get_neighbors_pos(pos) {
-                        q.push_front(ele);
-                    }
-                }
-            }
-        }
-        edge_reachable_set
+        //we start at every edge cell so everything reachable from them is found
+        let starts = self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| *e != colision_elem)
+            .map(|(i, _)| Coord::from_pos(i, self.width))
+            .filter(|c| c.x == 0 || c.y == 0 || c.x == self.width - 1 || c.y == self.height - 1);
+        let successors = |c: &Coord| {
+            self.neighbors4(c)
+                .filter(|(_, e)| *e != colision_elem)
+                .map(|(next, _)| next)
+        };
+        search::bfs(starts, successors, |_| false)
+            .distances
+            .keys()
+            .map(|c| c.to_pos(self.width))
+            .collect()
     }
 }
 impl<E: Clone> Grid<E> {
@@ -106,20 +231,190 @@ impl<E: Clone> Grid<E> {
         }
     }
 
-    pub fn rotate(&self) -> Grid<E> {
-        let mut new_data = Vec::with_capacity(self.width * self.height);
+    pub fn transpose(&self) -> Grid<E> {
+        self.view().transposed().to_grid()
+    }
+
+    pub fn rotate_cw(&self) -> Grid<E> {
+        self.view().rotated_cw().to_grid()
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<E> {
+        self.view().rotated_ccw().to_grid()
+    }
 
-        for col in (0..self.width).rev() {
-            // Iterate over columns in reverse order
-            for row in 0..self.height {
-                new_data.push(self.data[row * self.width + col].clone());
+    // quarter turns clockwise, negative ones turn counter clockwise
+    pub fn rotate(&self, turns: isize) -> Grid<E> {
+        match turns.rem_euclid(4) {
+            1 => self.rotate_cw(),
+            2 => {
+                let mut grid = self.clone();
+                grid.rotate_180();
+                grid
             }
+            3 => self.rotate_ccw(),
+            _ => self.clone(),
         }
+    }
+}
+
+impl<E: Clone + Ord> Grid<E> {
+    // the smallest of the 8 rotations and flips of the grid, so grids that
+    // only differ by one of them have the same canonical form
+    pub fn canonical(&self) -> Grid<E> {
+        let view = self.view();
+        let flipped = view.flipped_horizontal();
+        [view, flipped]
+            .into_iter()
+            .flat_map(|v| {
+                [
+                    v,
+                    v.rotated_cw(),
+                    v.rotated_cw().rotated_cw(),
+                    v.rotated_ccw(),
+                ]
+            })
+            .map(|v| v.to_grid())
+            .min_by(|a, b| (a.width, &a.data).cmp(&(b.width, &b.data)))
+            .unwrap_or_else(|| self.clone())
+    }
+}
+
+// in place transforms, they never need to copy a cell
+impl<E> Grid<E> {
+    // mirrors west and east
+    pub fn flip_horizontal(&mut self) {
+        for row in self.data.chunks_mut(self.width.max(1)) {
+            row.reverse();
+        }
+    }
+
+    // mirrors north and south
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.data.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    pub fn rotate_180(&mut self) {
+        self.data.reverse();
+    }
+
+    pub fn view(&self) -> GridView<'_, E> {
+        GridView {
+            grid: self,
+            is_transposed: false,
+            is_flipped_x: false,
+            is_flipped_y: false,
+        }
+    }
+}
+
+// A grid seen transposed, flipped or rotated without copying it, the view
+// flips its own coords first and then swaps them if it is transposed
+#[derive(Debug)]
+pub struct GridView<'a, E> {
+    grid: &'a Grid<E>,
+    is_transposed: bool,
+    is_flipped_x: bool,
+    is_flipped_y: bool,
+}
+
+impl<E> Clone for GridView<'_, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for GridView<'_, E> {}
+
+impl<'a, E> GridView<'a, E> {
+    pub fn width(&self) -> usize {
+        if self.is_transposed {
+            self.grid.height
+        } else {
+            self.grid.width
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        if self.is_transposed {
+            self.grid.width
+        } else {
+            self.grid.height
+        }
+    }
+
+    pub fn get(&self, coord: &Coord) -> Option<&'a E> {
+        if coord.x >= self.width() || coord.y >= self.height() {
+            return None;
+        }
+        let x = if self.is_flipped_x {
+            self.width() - 1 - coord.x
+        } else {
+            coord.x
+        };
+        let y = if self.is_flipped_y {
+            self.height() - 1 - coord.y
+        } else {
+            coord.y
+        };
+        let source = if self.is_transposed {
+            Coord::from(y, x)
+        } else {
+            Coord::from(x, y)
+        };
+        self.grid.get(&source)
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &'a E> + '_ {
+        (0..self.width()).filter_map(move |x| self.get(&Coord::from(x, y)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &'a E> + '_> + '_ {
+        (0..self.height()).map(move |y| self.row(y))
+    }
+
+    pub fn transposed(self) -> Self {
+        GridView {
+            grid: self.grid,
+            is_transposed: !self.is_transposed,
+            is_flipped_x: self.is_flipped_y,
+            is_flipped_y: self.is_flipped_x,
+        }
+    }
+
+    pub fn flipped_horizontal(mut self) -> Self {
+        self.is_flipped_x = !self.is_flipped_x;
+        self
+    }
+
+    pub fn flipped_vertical(mut self) -> Self {
+        self.is_flipped_y = !self.is_flipped_y;
+        self
+    }
 
+    pub fn rotated_cw(self) -> Self {
+        self.transposed().flipped_horizontal()
+    }
+
+    pub fn rotated_ccw(self) -> Self {
+        self.transposed().flipped_vertical()
+    }
+
+    pub fn rotated_180(self) -> Self {
+        self.flipped_horizontal().flipped_vertical()
+    }
+
+    pub fn to_grid(&self) -> Grid<E>
+    where
+        E: Clone,
+    {
         Grid {
-            data: new_data,
-            width: self.height,
-            height: self.width,
+            data: self.rows().flatten().cloned().collect(),
+            width: self.width(),
+            height: self.height(),
         }
     }
 }
@@ -129,22 +424,36 @@ where
     E: FromStr + Clone,
     <E as FromStr>::Err: Debug,
 {
-    pub fn new(text: &[String]) -> Result<Grid<E>> {
+    // one cell per space separated token
+    pub fn new(text: &[&str]) -> Result<Grid<E>> {
         if text.is_empty() {
-            bail!("Empty string")
+            return Err(AocError::EmptyInput.into());
+        }
+        let mut width = None;
+        let mut data: Vec<E> = vec![];
+        for (i, line) in text.iter().enumerate() {
+            let tokens: Vec<&str> = line.split(' ').filter(|s| !s.is_empty()).collect();
+            let expected = *width.get_or_insert(tokens.len());
+            if tokens.len() != expected {
+                bail!(AocError::parse(
+                    1,
+                    format!("Row has {} cells, expected {}", tokens.len(), expected)
+                )
+                .at_line(i));
+            }
+            for s in tokens {
+                let e = s.parse().map_err(|e| {
+                    AocError::parse(column_of(line, s), format!("Invalid cell [{}]: {:?}", s, e))
+                        .at_line(i)
+                })?;
+                data.push(e);
+            }
         }
-        let width = text.first().unwrap().len();
-        let data: Vec<E> = text
-            .iter()
-            .filter(|l| !l.is_empty())
-            .flat_map(|l| l.split(" "))
-            .map(|s| s.parse().expect("Could not turn string to specified type"))
-            .collect();
 
         Ok(Grid {
-            height: data.len() / width,
             data,
-            width,
+            width: width.unwrap_or(0),
+            height: text.len(),
         })
     }
 
@@ -207,46 +516,73 @@ impl Direction {
         }
     }
 
+    pub fn delta(&self) -> Point {
+        let (x, y) = self.coord_delta();
+        Point::from(x as i64, y as i64)
+    }
+
     pub fn all_dir() -> Vec<Direction> {
         vec![Self::North, Self::South, Self::East, Self::West]
     }
 }
 
-#[derive(Debug)]
-pub struct Coord {
-    pub x: usize,
-    pub y: usize,
+// x grows to the east and y to the south, grids index it as `Coord<usize>`
+// while walks that may leave the grid use signed points
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Coord<T = usize> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Coord {
-    pub fn from(x: usize, y: usize) -> Self {
+pub type Point<T = i64> = Coord<T>;
+
+impl<T> Coord<T> {
+    pub fn from(x: T, y: T) -> Self {
         Self { x, y }
     }
+}
 
-    pub fn from_pos(pos: usize, width: usize) -> Self {
-        let x = pos % width;
-        let y = (pos - x) / width;
-        Coord::from(x, y)
+impl<T: Copy> Coord<T> {
+    // None when a component does not fit in `U`, like a negative point
+    // turned into a grid coord
+    pub fn convert<U: TryFrom<T>>(&self) -> Option<Coord<U>> {
+        Some(Coord {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+        })
     }
+}
 
-    pub fn to_pos(&self, width: usize) -> usize {
-        (self.y * width) + self.x
-    }
-    pub fn is_after(&self, other: &Self) -> bool {
-        self.x > other.x || self.y > other.y
+impl<T: Copy + PartialOrd + Sub<Output = T>> Coord<T> {
+    pub fn manhattan(&self, other: &Self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
     }
 
-    pub fn plus_delta(&self, delta: (i32, i32)) -> Result<Coord> {
-        if self.x == 0 && delta.0 < 0 || self.y == 0 && delta.1 < 0 {
-            bail!("delta overflow")
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
         }
+    }
+}
 
-        Ok(Self {
-            x: usize_add(self.x, delta.0),
-            y: usize_add(self.y, delta.1),
-        })
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
     }
+}
 
+impl<T: PartialOrd> Coord<T> {
+    pub fn is_after(&self, other: &Self) -> bool {
+        self.x > other.x || self.y > other.y
+    }
     pub fn is_left_of(&self, other: &Self) -> bool {
         self.x < other.x
     }
@@ -261,10 +597,248 @@ impl Coord {
     }
 }
 
-fn usize_add(a: usize, b: i32) -> usize {
-    if b.is_negative() {
-        a - (b.abs() as u8) as usize
-    } else {
-        a + b as usize
+impl Coord {
+    pub fn from_pos(pos: usize, width: usize) -> Self {
+        let x = pos % width;
+        let y = (pos - x) / width;
+        Coord::from(x, y)
+    }
+
+    pub fn to_pos(&self, width: usize) -> usize {
+        (self.y * width) + self.x
+    }
+
+    // None when the move would take the coord below zero or past usize::MAX
+    pub fn checked_add(&self, delta: Point) -> Option<Coord> {
+        Some(Coord {
+            x: self.x.checked_add_signed(isize::try_from(delta.x).ok()?)?,
+            y: self.y.checked_add_signed(isize::try_from(delta.y).ok()?)?,
+        })
+    }
+
+    pub fn plus_delta(&self, delta: (i32, i32)) -> Result<Coord> {
+        match self.checked_add(Point::from(delta.0 as i64, delta.1 as i64)) {
+            Some(coord) => Ok(coord),
+            None => bail!("Moving {:?} by {:?} leaves the grid", self, delta),
+        }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Coord<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Coord::from(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Coord<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Coord::from(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Coord<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Coord::from(self.x * factor, self.y * factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = String;
+
+        fn try_from(c: char) -> std::result::Result<Self, Self::Error> {
+            match c {
+                '.' => Ok(Tile::Open),
+                '#' => Ok(Tile::Wall),
+                _ => Err(format!("Unexpected char [{}]", c)),
+            }
+        }
+    }
+
+    fn parse_error(result: Result<Grid<Tile>>) -> AocError {
+        result
+            .err()
+            .and_then(|e| e.downcast::<AocError>().ok())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_from_chars() -> Result<()> {
+        let grid: Grid<Tile> = Grid::from_chars(&["#.#", "..#"])?;
+        assert_eq!((3, 2), (grid.width, grid.height));
+        assert_eq!(Tile::Wall, grid.data[5]);
+        assert_eq!(
+            AocError::Parse {
+                line: 2,
+                column: 2,
+                message: "Unexpected char [x]".to_string()
+            },
+            parse_error(Grid::from_chars(&["#.#", ".x#"]))
+        );
+        assert_eq!(AocError::EmptyInput, parse_error(Grid::from_chars(&[])));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        assert_eq!(
+            AocError::Parse {
+                line: 3,
+                column: 3,
+                message: "Row has 2 chars, expected 3".to_string()
+            },
+            parse_error(Grid::from_chars(&["#.#", "..#", "##"]))
+        );
+    }
+
+    #[test]
+    fn test_from_chars_with() -> Result<()> {
+        let grid: Grid<u32> = Grid::from_chars_with(&["12", "34"], |c| {
+            c.to_digit(10).ok_or_else(|| anyhow!("Not a digit"))
+        })?;
+        assert_eq!(vec![1, 2, 3, 4], grid.data);
+        Ok(())
+    }
+
+    #[test]
+    fn test_new() -> Result<()> {
+        let grid: Grid<u32> = Grid::new(&["1 2 3", "4 5 6"])?;
+        assert_eq!((3, 2), (grid.width, grid.height));
+        let err = Grid::<u32>::new(&["1 2 3", "4 5"])
+            .err()
+            .and_then(|e| e.downcast::<AocError>().ok());
+        assert!(matches!(err, Some(AocError::Parse { line: 2, .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_coord_ops() {
+        let a = Point::from(3, -2);
+        let b = Point::from(-1, 4);
+        assert_eq!(Point::from(2, 2), a + b);
+        assert_eq!(Point::from(4, -6), a - b);
+        assert_eq!(Point::from(9, -6), a * 3);
+        assert_eq!(10, a.manhattan(&b));
+        assert_eq!(6, a.chebyshev(&b));
+        assert_eq!(4, Coord::from(1_usize, 5).manhattan(&Coord::from(3, 3)));
+    }
+
+    #[test]
+    fn test_checked_moves() {
+        let c = Coord::from(300, 2);
+        assert_eq!(Some(Coord::from(0, 2)), c.checked_add(Point::from(-300, 0)));
+        assert_eq!(None, c.checked_add(Point::from(-301, 0)));
+        assert_eq!(Coord::from(0, 2), c.plus_delta((-300, 0)).unwrap());
+        assert!(c.plus_delta((0, -3)).is_err());
+        assert_eq!(None, Point::from(-1, 2).convert::<usize>());
+        assert_eq!(Some(Coord::from(1_usize, 2)), Point::from(1, 2).convert());
+    }
+
+    #[test]
+    fn test_coord_of() -> Result<()> {
+        let grid: Grid<Tile> = Grid::from_chars(&["#.#", "..#"])?;
+        assert_eq!(Some(Coord::from(2, 1)), grid.coord_of(&Point::from(2, 1)));
+        assert_eq!(None, grid.coord_of(&Point::from(3, 1)));
+        assert_eq!(None, grid.coord_of(&Point::from(0, -1)));
+        assert_eq!(Some(&Tile::Open), grid.get(&Coord::from(1, 0)));
+        assert_eq!(None, grid.get(&Coord::from(0, 2)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbors() -> Result<()> {
+        let grid: Grid<u32> = Grid::new(&["1 2 3", "4 5 6", "7 8 9"])?;
+        let values = |it: Neighbors<'_, u32>| it.map(|(_, v)| *v).collect::<Vec<u32>>();
+        assert_eq!(
+            vec![2, 8, 6, 4],
+            values(grid.neighbors4(&Coord::from(1, 1)))
+        );
+        assert_eq!(vec![4, 2], values(grid.neighbors4(&Coord::from(0, 0))));
+        assert_eq!(8, grid.neighbors8(&Coord::from(1, 1)).count());
+        assert_eq!(vec![2, 4, 5], values(grid.neighbors8(&Coord::from(0, 0))));
+        assert_eq!(
+            vec![(Coord::from(2, 1), &6)],
+            grid.stencil(&Coord::from(0, 1), &[Point::from(2, 0)])
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapping_neighbors() -> Result<()> {
+        let grid: Grid<u32> = Grid::new(&["1 2 3", "4 5 6", "7 8 9"])?;
+        let wrapped: Vec<u32> = grid
+            .neighbors4(&Coord::from(0, 0))
+            .wrapping()
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(vec![7, 4, 2, 3], wrapped);
+        assert_eq!(8, grid.neighbors8(&Coord::from(2, 2)).wrapping().count());
+        Ok(())
+    }
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::from_chars(rows).unwrap()
+    }
+
+    #[test]
+    fn test_transforms() {
+        // 1 2 3
+        // 4 5 6
+        let g = grid(&["123", "456"]);
+        assert_eq!(grid(&["14", "25", "36"]), g.transpose());
+        assert_eq!(grid(&["41", "52", "63"]), g.rotate_cw());
+        assert_eq!(grid(&["36", "25", "14"]), g.rotate_ccw());
+        assert_eq!(grid(&["654", "321"]), g.rotate(2));
+        assert_eq!(g.rotate_ccw(), g.rotate(-1));
+        assert_eq!(g.rotate_ccw(), g.rotate(3));
+        assert_eq!(g, g.rotate(4));
+
+        let mut flipped = g.clone();
+        flipped.flip_horizontal();
+        assert_eq!(grid(&["321", "654"]), flipped);
+        let mut flipped = grid(&["12", "34", "56"]);
+        flipped.flip_vertical();
+        assert_eq!(grid(&["56", "34", "12"]), flipped);
+    }
+
+    #[test]
+    fn test_views() {
+        let g = grid(&["123", "456"]);
+        let view = g.view().rotated_cw();
+        assert_eq!((2, 3), (view.width(), view.height()));
+        assert_eq!(Some(&'4'), view.get(&Coord::from(0, 0)));
+        assert_eq!(None, view.get(&Coord::from(2, 0)));
+        assert_eq!(vec!['5', '2'], view.row(1).copied().collect::<Vec<char>>());
+        assert_eq!(g, view.rotated_ccw().to_grid());
+        assert_eq!(g.rotate(2), g.view().rotated_180().to_grid());
+        assert_eq!(
+            g.transpose(),
+            g.view().flipped_horizontal().rotated_ccw().to_grid()
+        );
+    }
+
+    #[test]
+    fn test_canonical() {
+        let g = grid(&["#..", "##."]);
+        let canonical = g.canonical();
+        assert_eq!(canonical, g.rotate_cw().canonical());
+        assert_eq!(canonical, g.transpose().canonical());
+        assert_ne!(canonical, grid(&["#.#", "##."]).canonical());
     }
 }
@@ -0,0 +1,346 @@
+use std::cmp::{max, min};
+
+// Half open interval [start, end), it is empty when end <= start
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Interval {
+    pub fn new(start: usize, end: usize) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn from_len(start: usize, len: usize) -> Self {
+        Interval::new(start, start + len)
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let result = Interval::new(max(self.start, other.start), min(self.end, other.end));
+        (!result.is_empty()).then_some(result)
+    }
+
+    // the part below `at` and the part from `at` on
+    pub fn split_at(&self, at: usize) -> (Option<Interval>, Option<Interval>) {
+        let below = Interval::new(self.start, min(self.end, at));
+        let above = Interval::new(max(self.start, at), self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    // what is left of this interval once `other` is taken out of it
+    pub fn difference(&self, other: &Interval) -> Vec<Interval> {
+        if self.intersect(other).is_none() {
+            return vec![*self];
+        }
+        let (below, _) = self.split_at(other.start);
+        let (_, above) = self.split_at(other.end);
+        below.into_iter().chain(above).collect()
+    }
+}
+
+// A set of values stored as sorted, disjoint and non adjacent intervals
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    intervals: Vec<Interval>,
+}
+
+impl RangeSet {
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // number of values in the set
+    pub fn len(&self) -> usize {
+        self.intervals.iter().map(|i| i.len()).sum()
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        self.intervals.push(interval);
+        self.intervals = merge(std::mem::take(&mut self.intervals));
+    }
+
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        self.intervals
+            .iter()
+            .chain(other.intervals.iter())
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        self.intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().filter_map(|b| a.intersect(b)))
+            .collect()
+    }
+
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut left = self.intervals.clone();
+        for taken in &other.intervals {
+            left = left.iter().flat_map(|i| i.difference(taken)).collect();
+        }
+        left.into_iter().collect()
+    }
+}
+
+// sorts the intervals and joins the ones that overlap or touch, empty ones
+// are dropped
+fn merge(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|i| !i.is_empty());
+    intervals.sort_unstable();
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for next in intervals {
+        match merged.last_mut() {
+            Some(last) if next.start <= last.end => last.end = max(last.end, next.end),
+            _ => merged.push(next),
+        }
+    }
+    merged
+}
+
+impl FromIterator<Interval> for RangeSet {
+    // sorted and merged once, not on every interval
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        RangeSet {
+            intervals: merge(iter.into_iter().collect()),
+        }
+    }
+}
+
+// An N dimensional box, the values whose every coordinate is in the interval
+// of its dimension
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Hyperbox<const N: usize> {
+    pub dims: [Interval; N],
+}
+
+impl<const N: usize> Hyperbox<N> {
+    pub fn new(dims: [Interval; N]) -> Self {
+        Hyperbox { dims }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dims.iter().any(|d| d.is_empty())
+    }
+
+    // number of points inside the box
+    pub fn volume(&self) -> usize {
+        self.dims.iter().map(|d| d.len()).product()
+    }
+
+    // the part of the box below `at` in dimension `dim` and the part from `at`
+    // on, the other dimensions are left as they are
+    pub fn split_at(&self, dim: usize, at: usize) -> (Option<Self>, Option<Self>) {
+        let (below, above) = self.dims[dim].split_at(at);
+        let with = |interval: Interval| {
+            let mut dims = self.dims;
+            dims[dim] = interval;
+            Hyperbox { dims }
+        };
+        (below.map(with), above.map(with))
+    }
+}
+
+// Moves the `range` values starting at `source` to start at `destination`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mapping {
+    pub source: usize,
+    pub destination: usize,
+    pub range: usize,
+}
+
+impl Mapping {
+    fn source_interval(&self) -> Interval {
+        Interval::from_len(self.source, self.range)
+    }
+
+    fn shift(&self, interval: &Interval) -> Interval {
+        Interval::new(
+            interval.start - self.source + self.destination,
+            interval.end - self.source + self.destination,
+        )
+    }
+}
+
+// A function made of mappings, values no mapping covers are left as they are
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PiecewiseMap {
+    mappings: Vec<Mapping>,
+}
+
+impl PiecewiseMap {
+    pub fn new(mappings: Vec<Mapping>) -> Self {
+        PiecewiseMap { mappings }
+    }
+
+    pub fn map(&self, value: usize) -> usize {
+        self.mappings
+            .iter()
+            .find(|m| m.source_interval().contains(value))
+            .map(|m| value - m.source + m.destination)
+            .unwrap_or(value)
+    }
+
+    // maps whole intervals at once instead of value by value, a value is only
+    // moved by the first mapping that covers it, as in `map`
+    pub fn map_set(&self, set: &RangeSet) -> RangeSet {
+        let mut mapped: Vec<Interval> = vec![];
+        let mut unmapped = set.clone();
+        for mapping in &self.mappings {
+            let source: RangeSet = [mapping.source_interval()].into_iter().collect();
+            for interval in unmapped.intersection(&source).intervals() {
+                mapped.push(mapping.shift(interval));
+            }
+            unmapped = unmapped.difference(&source);
+        }
+        mapped.extend_from_slice(unmapped.intervals());
+        mapped.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(usize, usize)]) -> RangeSet {
+        intervals
+            .iter()
+            .map(|(s, e)| Interval::new(*s, *e))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 8);
+        assert_eq!(6, a.len());
+        assert!(a.contains(2) && !a.contains(8));
+        assert_eq!(
+            Some(Interval::new(5, 8)),
+            a.intersect(&Interval::new(5, 10))
+        );
+        assert_eq!(None, a.intersect(&Interval::new(8, 10)));
+        assert_eq!(
+            (Some(Interval::new(2, 4)), Some(Interval::new(4, 8))),
+            a.split_at(4)
+        );
+        assert_eq!((None, Some(a)), a.split_at(1));
+        assert_eq!(
+            vec![Interval::new(2, 3), Interval::new(5, 8)],
+            a.difference(&Interval::new(3, 5))
+        );
+        assert!(a.difference(&Interval::new(0, 10)).is_empty());
+    }
+
+    #[test]
+    fn test_range_set() {
+        let a = set(&[(0, 5), (3, 7), (10, 12), (12, 13)]);
+        assert_eq!(&[Interval::new(0, 7), Interval::new(10, 13)], a.intervals());
+        assert_eq!(10, a.len());
+        let b = set(&[(6, 11)]);
+        assert_eq!(set(&[(0, 13)]), a.union(&b));
+        assert_eq!(set(&[(6, 7), (10, 11)]), a.intersection(&b));
+        assert_eq!(set(&[(0, 6), (11, 13)]), a.difference(&b));
+        assert_eq!(Some(0), a.min());
+        assert!(a.contains(12) && !a.contains(8));
+        let mut c = set(&[(4, 4), (8, 9)]);
+        c.insert(Interval::new(2, 5));
+        c.insert(Interval::new(5, 8));
+        assert_eq!(set(&[(2, 9)]), c);
+    }
+
+    #[test]
+    fn test_piecewise_map() {
+        // seed-to-soil map of the day 5 example
+        let map = PiecewiseMap::new(vec![
+            Mapping {
+                source: 98,
+                destination: 50,
+                range: 2,
+            },
+            Mapping {
+                source: 50,
+                destination: 52,
+                range: 48,
+            },
+        ]);
+        assert_eq!(81, map.map(79));
+        assert_eq!(51, map.map(99));
+        // the first value past a mapping is not mapped anymore
+        assert_eq!(100, map.map(100));
+        assert_eq!(
+            set(&[(48, 50), (52, 54), (100, 102)]),
+            map.map_set(&set(&[(48, 50), (50, 52), (100, 102)]))
+        );
+        assert_eq!(set(&[(50, 52), (98, 100)]), map.map_set(&set(&[(96, 100)])));
+    }
+
+    #[test]
+    fn test_overlapping_mappings() {
+        // 5..10 is covered by both mappings, only the first one moves it
+        let map = PiecewiseMap::new(vec![
+            Mapping {
+                source: 0,
+                destination: 100,
+                range: 10,
+            },
+            Mapping {
+                source: 5,
+                destination: 200,
+                range: 10,
+            },
+        ]);
+        assert_eq!(107, map.map(7));
+        assert_eq!(set(&[(100, 110)]), map.map_set(&set(&[(0, 10)])));
+        assert_eq!(
+            set(&[(15, 20), (100, 110), (205, 210)]),
+            map.map_set(&set(&[(0, 20)]))
+        );
+        let values: RangeSet = (0..20).map(|v| Interval::from_len(map.map(v), 1)).collect();
+        assert_eq!(values, map.map_set(&set(&[(0, 20)])));
+    }
+
+    #[test]
+    fn test_hyperbox() {
+        let cube = Hyperbox::new([Interval::new(0, 4), Interval::new(0, 3)]);
+        assert_eq!(12, cube.volume());
+        let (below, above) = cube.split_at(0, 1);
+        assert_eq!(Some(3), below.map(|b| b.volume()));
+        assert_eq!(
+            Some(Hyperbox::new([Interval::new(1, 4), Interval::new(0, 3)])),
+            above
+        );
+        assert_eq!((Some(cube), None), cube.split_at(1, 5));
+        assert!(Hyperbox::new([Interval::new(2, 2), Interval::new(0, 3)]).is_empty());
+    }
+}
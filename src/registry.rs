@@ -0,0 +1,117 @@
+use anyhow::Result;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::input::Input;
+use crate::prelude::Grid;
+use crate::solution::{DayPart, Solution};
+use crate::{
+    day_10_1, day_11_1, day_12_1, day_13_1, day_14, day_15, day_16, day_17, day_18, day_19,
+    day_2_1, day_3_1, day_4_1, day_5_1, day_6_1, day_7_1, day_8_1, day_9_1, trebuchet,
+};
+
+// Every solved day, in calendar order. Adding a day only needs a new entry here
+pub static DAYS: &[Day] = &[
+    Day::of::<trebuchet::Day1>(),
+    Day::of::<day_2_1::Day2>(),
+    Day::of::<day_3_1::Day3>(),
+    Day::of::<day_4_1::Day4>(),
+    Day::of::<day_5_1::Day5>(),
+    Day::of::<day_6_1::Day6>(),
+    Day::of::<day_7_1::Day7>(),
+    Day::of::<day_8_1::Day8>(),
+    Day::of::<day_9_1::Day9>(),
+    Day::of::<day_10_1::Day10>(),
+    Day::of::<day_11_1::Day11>(),
+    Day::of::<day_12_1::Day12>(),
+    Day::of::<day_13_1::Day13>(),
+    Day::of::<day_14::Day14>(),
+    Day::of::<day_15::Day15>(),
+    Day::of::<day_16::Day16>(),
+    Day::of::<day_17::Day17>(),
+    Day::of::<day_18::Day18>(),
+    Day::of::<day_19::Day19>(),
+];
+
+pub fn find(day: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+// Type erased input of a day, already parsed and ready to be solved
+pub trait ParsedInput {
+    fn solve(&self, part: DayPart) -> Result<Answer>;
+    fn render(&self, part: DayPart) -> Result<Option<Grid<char>>>;
+}
+
+struct Parsed<'a, S: Solution>(S::Parsed<'a>);
+
+impl<S: Solution> ParsedInput for Parsed<'_, S> {
+    fn solve(&self, part: DayPart) -> Result<Answer> {
+        match part {
+            DayPart::One => S::part_one(&self.0),
+            DayPart::Two => S::part_two(&self.0),
+        }
+    }
+
+    fn render(&self, part: DayPart) -> Result<Option<Grid<char>>> {
+        S::render(&self.0, part)
+    }
+}
+
+fn parse_with<S: Solution + 'static>(input: &Input) -> Result<Box<dyn ParsedInput + '_>> {
+    Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+}
+
+pub struct Day {
+    pub day: usize,
+    parse: for<'a> fn(&'a Input) -> Result<Box<dyn ParsedInput + 'a>>,
+}
+
+impl Day {
+    pub const fn of<S: Solution + 'static>() -> Day {
+        Day {
+            day: S::DAY,
+            parse: parse_with::<S>,
+        }
+    }
+
+    pub fn parse<'a>(&self, input: &'a Input) -> Result<Box<dyn ParsedInput + 'a>> {
+        if input.is_blank() {
+            return Err(AocError::EmptyInput.into());
+        }
+        (self.parse)(input)
+    }
+
+    pub fn run(&self, input: &Input, part: DayPart) -> Result<Answer> {
+        self.parse(input)?.solve(part)
+    }
+
+    pub fn render(&self, input: &Input, part: DayPart) -> Result<Option<Grid<char>>> {
+        self.parse(input)?.render(part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_in_calendar_order() {
+        let days: Vec<usize> = DAYS.iter().map(|d| d.day).collect();
+        let expect: Vec<usize> = (1..=19).collect();
+        assert_eq!(expect, days);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(Some(15), find(15).map(|d| d.day));
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let input = Input::from("\n \n");
+        let err = find(1).unwrap().run(&input, DayPart::One).unwrap_err();
+        assert_eq!(Some(&AocError::EmptyInput), err.downcast_ref::<AocError>());
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::prelude::{Coord, Grid};
+
+pub type Rgb = [u8; 3];
+
+// A grid drawn with some coords replaced by highlight chars, like a beam path
+// or a trench, layers added later are drawn on top of the earlier ones
+pub struct Overlay<'a, E> {
+    grid: &'a Grid<E>,
+    marks: HashMap<usize, char>,
+}
+
+impl<E> Grid<E> {
+    pub fn overlay(&self) -> Overlay<'_, E> {
+        Overlay {
+            grid: self,
+            marks: HashMap::new(),
+        }
+    }
+}
+
+impl<E: Display> Overlay<'_, E> {
+    pub fn highlight(mut self, coords: impl IntoIterator<Item = Coord>, mark: char) -> Self {
+        for coord in coords {
+            if self.grid.is_within_bounds(&coord) {
+                self.marks.insert(coord.to_pos(self.grid.width), mark);
+            }
+        }
+        self
+    }
+
+    // one char per cell, cells that display as more than one char keep the
+    // first one
+    pub fn to_chars(&self) -> Grid<char> {
+        let data = self
+            .grid
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, e)| match self.marks.get(&i) {
+                Some(mark) => *mark,
+                None => e.to_string().chars().next().unwrap_or(' '),
+            })
+            .collect();
+        Grid {
+            data,
+            width: self.grid.width,
+            height: self.grid.height,
+        }
+    }
+}
+
+impl<E: Display> Display for Overlay<'_, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, e) in self.grid.data.iter().enumerate() {
+            if i > 0 && i % self.grid.width == 0 {
+                writeln!(f)?;
+            }
+            match self.marks.get(&i) {
+                Some(mark) => write!(f, "{}", mark)?,
+                None => write!(f, "{}", e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// default colour map, background chars are dark, walls light and every other
+// char gets its own bright colour
+pub fn palette(c: char) -> Rgb {
+    match c {
+        '.' | ' ' => [16, 16, 24],
+        '#' => [220, 220, 220],
+        _ => {
+            let hash = (c as u32).wrapping_mul(2_654_435_761);
+            [
+                128 | (hash >> 24) as u8,
+                128 | (hash >> 16) as u8,
+                128 | (hash >> 8) as u8,
+            ]
+        }
+    }
+}
+
+impl<E> Grid<E> {
+    // binary PPM image with each cell as a `scale` x `scale` square
+    pub fn to_ppm(&self, scale: usize, colour: impl Fn(&E) -> Rgb) -> Vec<u8> {
+        let scale = scale.max(1);
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        image.reserve(width * height * 3);
+        for row in self.data.chunks(self.width.max(1)) {
+            let pixels: Vec<u8> = row.iter().flat_map(|e| colour(e).repeat(scale)).collect();
+            for _ in 0..scale {
+                image.extend_from_slice(&pixels);
+            }
+        }
+        image
+    }
+
+    pub fn save_ppm(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        colour: impl Fn(&E) -> Rgb,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_ppm(scale, colour))
+            .with_context(|| format!("Could not write image [{}]", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::from_chars(&["#..", ".#."]).unwrap()
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("#..\n.#.", grid().to_string());
+    }
+
+    #[test]
+    fn test_overlay() {
+        let grid = grid();
+        let overlay = grid
+            .overlay()
+            .highlight([Coord::from(1, 0), Coord::from(2, 1)], 'o')
+            .highlight([Coord::from(2, 1), Coord::from(5, 5)], '*');
+        assert_eq!("#o.\n.#*", overlay.to_string());
+        assert_eq!(vec!['#', 'o', '.', '.', '#', '*'], overlay.to_chars().data);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = grid().to_ppm(2, |c| palette(*c));
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&header[..], &image[..header.len()]);
+        assert_eq!(header.len() + 6 * 4 * 3, image.len());
+        // the second pixel is still the scaled up first cell
+        let pixels = &image[header.len()..];
+        assert_eq!(palette('#'), pixels[3..6]);
+        assert_eq!(palette('.'), pixels[6..9]);
+    }
+}
@@ -0,0 +1,394 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+// What a search found: the cost to reach every visited state, the state each
+// one was reached from and the goal, when there was one and it was reached
+#[derive(Debug)]
+pub struct SearchResult<S> {
+    pub distances: HashMap<S, usize>,
+    parents: HashMap<S, S>,
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash> SearchResult<S> {
+    fn new() -> Self {
+        SearchResult {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    // cost of the cheapest way to the goal
+    pub fn cost(&self) -> Option<usize> {
+        self.goal.as_ref().and_then(|goal| self.distance(goal))
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    // states from the start to the goal, both included
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// breadth first search where every step costs 1, it stops at the first state
+// `is_goal` accepts, pass `|_| false` to get the whole distance map
+pub fn bfs<S, N, I, G>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: N,
+    mut is_goal: G,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut result = SearchResult::new();
+    let mut queue: VecDeque<S> = VecDeque::new();
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        let distance = result.distances[&state] + 1;
+        for next in successors(&state) {
+            if result.distances.contains_key(&next) {
+                continue;
+            }
+            result.distances.insert(next.clone(), distance);
+            result.parents.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+    result
+}
+
+// cheapest paths when steps have a cost, `successors` returns each next state
+// with the cost of moving to it
+pub fn dijkstra<S, N, I, G>(
+    starts: impl IntoIterator<Item = S>,
+    successors: N,
+    is_goal: G,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+// dijkstra guided by `heuristic`, which must never overestimate the cost left
+// to reach the goal for the result to be the cheapest
+pub fn astar<S, N, I, H, G>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: N,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    H: FnMut(&S) -> usize,
+    G: FnMut(&S) -> bool,
+{
+    let mut result = SearchResult::new();
+    // the heap holds indexes into `states` so S does not need to be Ord
+    let mut states: Vec<S> = vec![];
+    let mut heap: BinaryHeap<Reverse<(usize, usize, usize)>> = BinaryHeap::new();
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            heap.push(Reverse((heuristic(&start), 0, states.len())));
+            states.push(start);
+        }
+    }
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if result.distances.get(&state).is_some_and(|d| *d < cost) {
+            // a cheaper way to this state was already expanded
+            continue;
+        }
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            if result.distances.get(&next).is_some_and(|d| *d <= next_cost) {
+                continue;
+            }
+            result.distances.insert(next.clone(), next_cost);
+            result.parents.insert(next.clone(), state.clone());
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                states.len(),
+            )));
+            states.push(next);
+        }
+    }
+    result
+}
+
+// Dial's priority queue, for costs that never grow by more than `max_step`
+// from the last popped one. A ring of buckets replaces the heap, so pushing
+// and popping are constant time
+pub struct BucketQueue<T> {
+    buckets: Vec<Vec<T>>,
+    cost: usize,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new(max_step: usize) -> Self {
+        BucketQueue {
+            buckets: (0..=max_step).map(|_| vec![]).collect(),
+            cost: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // panics when the cost is out of the window, the item would land in the
+    // wrong bucket and pop with a wrong cost
+    pub fn push(&mut self, cost: usize, item: T) {
+        assert!(
+            cost >= self.cost && cost - self.cost < self.buckets.len(),
+            "Cost {} out of the queue window starting at {}",
+            cost,
+            self.cost
+        );
+        let ring = self.buckets.len();
+        self.buckets[cost % ring].push(item);
+        self.len += 1;
+    }
+
+    // one of the items with the lowest cost, with that cost
+    pub fn pop(&mut self) -> Option<(usize, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        let ring = self.buckets.len();
+        loop {
+            if let Some(item) = self.buckets[self.cost % ring].pop() {
+                self.len -= 1;
+                return Some((self.cost, item));
+            }
+            self.cost += 1;
+        }
+    }
+}
+
+// Tarjan's strongly connected components of the graph whose node `i` has an
+// edge to every node of `edges[i]`. A component comes after every other
+// component it can reach, so they can be folded in order. The recursion is
+// kept on an explicit stack so long chains do not overflow it
+pub fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; edges.len()];
+    let mut low = vec![0; edges.len()];
+    let mut on_stack = vec![false; edges.len()];
+    let mut stack: Vec<usize> = vec![];
+    let mut components: Vec<Vec<usize>> = vec![];
+    let mut next_index = 0;
+
+    for root in 0..edges.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // nodes being visited with the next of their edges to look at
+        let mut calls: Vec<(usize, usize)> = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((node, edge)) = calls.last_mut() {
+            let node = *node;
+            if let Some(&next) = edges[node].get(*edge) {
+                *edge += 1;
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    low[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(index[next]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some((parent, _)) = calls.last() {
+                low[*parent] = low[*parent].min(low[node]);
+            }
+            if low[node] == index[node] {
+                let mut component = vec![];
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2
+    // |       |
+    // 3 ----- 4
+    fn edges(n: &u32) -> Vec<(u32, usize)> {
+        match n {
+            0 => vec![(1, 1), (3, 1)],
+            1 => vec![(0, 1), (2, 1)],
+            2 => vec![(1, 1), (4, 1)],
+            3 => vec![(0, 1), (4, 5)],
+            4 => vec![(2, 1), (3, 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let successors = |n: &u32| edges(n).into_iter().map(|(next, _)| next);
+        let result = bfs([0], successors, |_| false);
+        assert_eq!(5, result.distances.len());
+        assert_eq!(Some(2), result.distance(&4));
+        assert_eq!(None, result.cost());
+
+        let result = bfs([0], successors, |n| *n == 2);
+        assert_eq!(Some(2), result.cost());
+        assert_eq!(Some(vec![0, 1, 2]), result.path());
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra([0], edges, |n| *n == 4);
+        assert_eq!(Some(3), result.cost());
+        assert_eq!(Some(vec![0, 1, 2, 4]), result.path());
+        assert_eq!(Some(1), result.distance(&3));
+
+        let result = dijkstra([3], edges, |_| false);
+        assert_eq!(Some(4), result.distance(&4));
+        assert_eq!(Some(vec![3, 0, 1, 2, 4]), result.path_to(&4));
+        assert_eq!(None, result.path_to(&7));
+    }
+
+    #[test]
+    fn test_astar() {
+        // walking a line towards 10, the distance left is an exact heuristic
+        let successors = |n: &i64| vec![(n - 1, 1), (n + 1, 1)];
+        let result = astar(
+            [0],
+            successors,
+            |n| (10 - n).unsigned_abs() as usize,
+            |n| *n == 10,
+        );
+        assert_eq!(Some(10), result.cost());
+        assert_eq!(11, result.path().unwrap().len());
+        assert!(result.distances.len() < 15);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        // 0 -> 1 -> 2 -> 0 is a cycle that reaches 3 <-> 4, 5 reaches both
+        let edges = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3], vec![0, 4]];
+        let components: Vec<Vec<usize>> = strongly_connected_components(&edges)
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect();
+        assert_eq!(vec![vec![3, 4], vec![0, 1, 2], vec![5]], components);
+
+        // a long chain does not overflow the stack
+        let chain: Vec<Vec<usize>> = (0..100_000).map(|i| vec![i + 1]).chain([vec![]]).collect();
+        let components = strongly_connected_components(&chain);
+        assert_eq!(100_001, components.len());
+        assert_eq!(vec![100_000], components[0]);
+    }
+
+    #[test]
+    fn test_bucket_queue() {
+        let mut queue = BucketQueue::new(9);
+        queue.push(7, 'c');
+        queue.push(0, 'a');
+        queue.push(3, 'b');
+        assert_eq!(3, queue.len());
+        assert_eq!(Some((0, 'a')), queue.pop());
+        queue.push(9, 'e');
+        queue.push(3, 'B');
+        assert_eq!(Some((3, 'B')), queue.pop());
+        assert_eq!(Some((3, 'b')), queue.pop());
+        // the window moved on to 3, so 12 fits now
+        queue.push(12, 'f');
+        assert_eq!(Some((7, 'c')), queue.pop());
+        assert_eq!(Some((9, 'e')), queue.pop());
+        assert_eq!(Some((12, 'f')), queue.pop());
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.pop());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cost 13 out of the queue window starting at 3")]
+    fn test_bucket_queue_push_too_far() {
+        let mut queue = BucketQueue::new(9);
+        queue.push(3, 'a');
+        assert_eq!(Some((3, 'a')), queue.pop());
+        queue.push(13, 'b');
+    }
+
+    #[test]
+    #[should_panic(expected = "Cost 2 out of the queue window starting at 3")]
+    fn test_bucket_queue_push_below_popped() {
+        let mut queue = BucketQueue::new(9);
+        queue.push(3, 'a');
+        assert_eq!(Some((3, 'a')), queue.pop());
+        queue.push(2, 'b');
+    }
+}
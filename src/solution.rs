@@ -0,0 +1,78 @@
+use anyhow::{bail, Result};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::input::Input;
+use crate::prelude::Grid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DayPart {
+    One,
+    Two,
+}
+
+impl DayPart {
+    pub fn number(&self) -> usize {
+        match self {
+            DayPart::One => 1,
+            DayPart::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<usize> for DayPart {
+    type Error = anyhow::Error;
+    fn try_from(value: usize) -> Result<Self> {
+        match value {
+            1 => Ok(DayPart::One),
+            2 => Ok(DayPart::Two),
+            _ => bail!("Invalid day part [{}]", value),
+        }
+    }
+}
+
+// A day of the calendar: the input is parsed once into `Parsed`, which may
+// borrow from it, and both parts are solved from it
+pub trait Solution {
+    const DAY: usize;
+    type Parsed<'a>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>>;
+
+    fn part_one(input: &Self::Parsed<'_>) -> Result<Answer>;
+
+    fn part_two(_input: &Self::Parsed<'_>) -> Result<Answer> {
+        Err(AocError::UnknownPart {
+            day: Self::DAY,
+            part: 2,
+        }
+        .into())
+    }
+
+    // a drawing of the puzzle for a part, like a beam path or a trench, to see
+    // what the solution did; days without one return None
+    fn render(_input: &Self::Parsed<'_>, _part: DayPart) -> Result<Option<Grid<char>>> {
+        Ok(None)
+    }
+
+    fn solve(input: &Input, part: DayPart) -> Result<Answer> {
+        let parsed = Self::parse(input)?;
+        match part {
+            DayPart::One => Self::part_one(&parsed),
+            DayPart::Two => Self::part_two(&parsed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_part_from_number() {
+        assert_eq!(DayPart::One, DayPart::try_from(1).unwrap());
+        assert_eq!(DayPart::Two, DayPart::try_from(2).unwrap());
+        assert!(DayPart::try_from(3).is_err());
+        assert_eq!(2, DayPart::Two.number());
+    }
+}
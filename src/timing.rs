@@ -0,0 +1,210 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AocError;
+use crate::input::Input;
+use crate::registry;
+use crate::solution::DayPart;
+
+// A parse or solve that is slower than its baseline by more than this is a
+// regression, unless the difference is too small to tell apart from noise
+pub const REGRESSION_THRESHOLD: f64 = 0.2;
+const NOISE_NANOS: u64 = 50_000;
+
+// Wall time statistics of a set of runs, in nanoseconds
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: u64,
+    pub median: u64,
+    pub mean: u64,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Stats {
+        let mut nanos: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        nanos.sort_unstable();
+        if nanos.is_empty() {
+            return Stats {
+                min: 0,
+                median: 0,
+                mean: 0,
+            };
+        }
+        let mid = nanos.len() / 2;
+        let median = if nanos.len().is_multiple_of(2) {
+            (nanos[mid - 1] + nanos[mid]) / 2
+        } else {
+            nanos[mid]
+        };
+        Stats {
+            min: nanos[0],
+            median,
+            mean: nanos.iter().sum::<u64>() / nanos.len() as u64,
+        }
+    }
+
+    // relative change of the median, 0.5 means 50% slower
+    pub fn change_from(&self, baseline: &Stats) -> f64 {
+        let before = baseline.median.max(1) as f64;
+        (self.median as f64 - before) / before
+    }
+
+    pub fn is_regression_from(&self, baseline: &Stats) -> bool {
+        self.median > baseline.median + NOISE_NANOS
+            && self.change_from(baseline) > REGRESSION_THRESHOLD
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: usize,
+    pub part: usize,
+    pub runs: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Timing {
+    // parses and solves the input `runs` times, timing both steps apart
+    pub fn measure(day: usize, part: usize, input: &Input, runs: usize) -> Result<Timing> {
+        if runs == 0 {
+            bail!("At least one run is needed");
+        }
+        let entry = registry::find(day).ok_or(AocError::UnknownDay(day))?;
+        let day_part = DayPart::try_from(part).map_err(|_| AocError::UnknownPart { day, part })?;
+        let mut parse_samples: Vec<Duration> = Vec::with_capacity(runs);
+        let mut solve_samples: Vec<Duration> = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let start = Instant::now();
+            let parsed = entry.parse(input)?;
+            parse_samples.push(start.elapsed());
+
+            let start = Instant::now();
+            // kept opaque so the optimiser cannot drop the solve
+            std::hint::black_box(parsed.solve(day_part)?);
+            solve_samples.push(start.elapsed());
+        }
+        Ok(Timing {
+            day,
+            part,
+            runs,
+            parse: Stats::of(&parse_samples),
+            solve: Stats::of(&solve_samples),
+        })
+    }
+
+    // either step got slower
+    pub fn is_regression_from(&self, baseline: &Timing) -> bool {
+        self.parse.is_regression_from(&baseline.parse)
+            || self.solve.is_regression_from(&baseline.solve)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub timings: Vec<Timing>,
+}
+
+impl Timings {
+    pub fn load(path: &str) -> Result<Timings> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read timings file [{}]", path))?;
+        serde_json::from_str(&text).with_context(|| format!("Invalid timings file [{}]", path))
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, text).with_context(|| format!("Could not write timings [{}]", path))
+    }
+
+    pub fn find(&self, day: usize, part: usize) -> Option<&Timing> {
+        self.timings.iter().find(|t| t.day == day && t.part == part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median: u64) -> Stats {
+        Stats {
+            min: median,
+            median,
+            mean: median,
+        }
+    }
+
+    fn timing(median: u64) -> Timing {
+        timing_of(median, median)
+    }
+
+    fn timing_of(parse: u64, solve: u64) -> Timing {
+        Timing {
+            day: 1,
+            part: 1,
+            runs: 1,
+            parse: stats(parse),
+            solve: stats(solve),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [5, 1, 4, 2]
+            .iter()
+            .map(|n| Duration::from_nanos(*n))
+            .collect();
+        assert_eq!(
+            Stats {
+                min: 1,
+                median: 3,
+                mean: 3
+            },
+            Stats::of(&samples)
+        );
+        assert_eq!(2, Stats::of(&samples[1..]).median);
+    }
+
+    #[test]
+    fn test_measure() -> Result<()> {
+        let input = Input::from("1abc2\ntreb7uchet");
+        let timing = Timing::measure(1, 2, &input, 3)?;
+        assert_eq!((1, 2, 3), (timing.day, timing.part, timing.runs));
+        assert!(timing.solve.min <= timing.solve.median);
+        assert!(Timing::measure(10, 2, &input, 1).is_err());
+        assert!(Timing::measure(1, 1, &input, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_change_from() {
+        assert_eq!(0.5, stats(150).change_from(&stats(100)));
+        assert_eq!(-0.5, stats(50).change_from(&stats(100)));
+    }
+
+    #[test]
+    fn test_is_regression_from() {
+        assert!(timing(300_000).is_regression_from(&timing(200_000)));
+        assert!(!timing(230_000).is_regression_from(&timing(200_000)));
+        assert!(!timing(20_000).is_regression_from(&timing(1_000)));
+        // a slower parse fails the same as a slower solve
+        let baseline = timing_of(200_000, 200_000);
+        assert!(timing_of(300_000, 200_000).is_regression_from(&baseline));
+        assert!(timing_of(200_000, 300_000).is_regression_from(&baseline));
+        assert!(!timing_of(230_000, 150_000).is_regression_from(&baseline));
+    }
+
+    #[test]
+    fn test_json_round_trip() -> Result<()> {
+        let timings = Timings {
+            timings: vec![timing(10)],
+        };
+        let text = serde_json::to_string(&timings)?;
+        assert_eq!(timings, serde_json::from_str(&text)?);
+        assert!(timings.find(1, 1).is_some());
+        assert!(timings.find(1, 2).is_none());
+        Ok(())
+    }
+}
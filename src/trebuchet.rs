@@ -1,18 +1,42 @@
-fn main() {
-    let lines = std::fs::read_to_string("input.txt");
-    if let Ok(lines) = lines {
-        let mut sum = 0 as usize;
-        for line in lines.split("\n").into_iter() {
-            sum += parse_line(line.to_string());
-        }
-        println!("Result is [{}]", sum);
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::solution::Solution;
+use crate::trebuchet_2;
+use anyhow::Result;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: usize = 1;
+    // the parts read different digits out of the lines, spelled out ones
+    // only count in part two
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>> {
+        Ok(input.lines())
     }
+
+    fn part_one(input: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(process_lines(input)?.into())
+    }
+
+    fn part_two(input: &Self::Parsed<'_>) -> Result<Answer> {
+        Ok(trebuchet_2::process_lines(input)?.into())
+    }
+}
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    let mut sum = 0_usize;
+    for line in lines {
+        sum += parse_line(line);
+    }
+    Ok(sum)
 }
 
-fn parse_line(line: String) -> usize {
+fn parse_line(line: &str) -> usize {
     let mut first: Option<char> = None;
     let mut last: Option<char> = None;
-    for c in line.chars().into_iter() {
+    for c in line.chars() {
         if c.is_numeric() {
             last = Some(c);
             if first.is_none() {
@@ -33,10 +57,22 @@ fn parse_line(line: String) -> usize {
         number_text.push(c);
     }
 
-    let result = number_text.parse::<usize>();
+    number_text.parse::<usize>().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    match result {
-        Ok(num) => num,
-        Err(_) => 0,
+    #[test]
+    fn test_simple_input() {
+        let input = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
+        assert_eq!(142, result.unwrap());
     }
 }
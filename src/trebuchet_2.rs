@@ -1,12 +1,14 @@
-pub fn parse_lines(lines: Vec<String>) {
-    let mut sum = 0 as usize;
-    for line in lines.into_iter() {
-        sum += parse_line(line.to_string());
+use anyhow::Result;
+
+pub fn process_lines(lines: &[&str]) -> Result<usize> {
+    let mut sum = 0_usize;
+    for line in lines {
+        sum += parse_line(line);
     }
-    println!("Result is [{}]", sum);
+    Ok(sum)
 }
 
-pub fn parse_line(line: String) -> usize {
+pub fn parse_line(line: &str) -> usize {
     let word_map = vec![
         ("one", '1'),
         ("two", '2'),
@@ -20,8 +22,7 @@ pub fn parse_line(line: String) -> usize {
     ];
     let mut first: Option<char> = None;
     let mut last: Option<char> = None;
-    for i in 0..line.len() {
-        let c = line.chars().nth(i).unwrap();
+    for (i, c) in line.char_indices() {
         if c.is_numeric() {
             last = Some(c);
         } else {
@@ -46,10 +47,26 @@ pub fn parse_line(line: String) -> usize {
         number_text.push(c);
     }
 
-    let result = number_text.parse::<usize>();
+    number_text.parse::<usize>().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
 
-    match result {
-        Ok(num) => num,
-        Err(_) => 0,
+    #[test]
+    fn test_simple_input() {
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+        let input = Input::from(input);
+        let lines = input.lines();
+        let result = process_lines(&lines);
+        assert_eq!(281, result.unwrap());
     }
 }
@@ -0,0 +1,227 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+use advent_of_code::inputs::{self, EXAMPLES_DIR};
+use advent_of_code::{days, process_lines, Answer, Answers, AocError, Input, InputError};
+
+use crate::cli::{Args, InputSource};
+use crate::{describe_input_error, read_day_input};
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+    Error,
+}
+
+impl Status {
+    fn is_regression(&self) -> bool {
+        matches!(self, Status::Fail | Status::Error)
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+            Status::Error => "ERROR",
+        }
+    }
+}
+
+// None when there is nothing to report, a part that is not implemented and
+// has no stored answer
+fn status(expected: Option<&Answer>, result: &Result<Answer>) -> Option<Status> {
+    match (expected, result) {
+        (Some(e), Ok(a)) if e == a => Some(Status::Pass),
+        (Some(_), Ok(_)) => Some(Status::Fail),
+        (None, Ok(_)) => Some(Status::Missing),
+        (None, Err(e)) if matches!(e.downcast_ref(), Some(AocError::UnknownPart { .. })) => None,
+        (_, Err(_)) => Some(Status::Error),
+    }
+}
+
+struct Row {
+    day: usize,
+    input: String,
+    part: usize,
+    status: Status,
+    answer: String,
+    expected: String,
+}
+
+// runs every selected day and compares it with the stored answers, returns
+// false when any answer changed or failed
+pub fn verify(args: &Args) -> bool {
+    let answers = match Answers::load(&args.answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return false;
+        }
+    };
+    let selected_days = args.days.days(&days());
+    if selected_days.len() > 1 && args.input == InputSource::Stdin {
+        eprintln!("Reading from stdin is only supported for a single day");
+        return false;
+    }
+    let mut rows: Vec<Row> = vec![];
+    for day in selected_days {
+        let input = read_day_input(&args.input, day);
+        check_parts(&mut rows, day, "input", &input, &args.parts, &answers);
+    }
+    print_table(&rows);
+    !rows.iter().any(|row| row.status.is_regression())
+}
+
+// same as verify but against every example of the selected days
+pub fn verify_examples(args: &Args) -> bool {
+    let mut rows: Vec<Row> = vec![];
+    for day in args.days.days(&days()) {
+        let examples = match inputs::examples(Path::new(EXAMPLES_DIR), day) {
+            Ok(examples) => examples,
+            Err(e) => {
+                eprintln!("{:#}", e);
+                return false;
+            }
+        };
+        for example in examples {
+            let input = Input::from_file(&example.path);
+            let label = format!("example {}", example.number);
+            check_parts(
+                &mut rows,
+                day,
+                &label,
+                &input,
+                &args.parts,
+                &example.expected,
+            );
+        }
+    }
+    if rows.is_empty() {
+        eprintln!("No examples found for the selected days");
+        return false;
+    }
+    print_table(&rows);
+    !rows.iter().any(|row| row.status.is_regression())
+}
+
+fn check_parts(
+    rows: &mut Vec<Row>,
+    day: usize,
+    label: &str,
+    input: &Result<Input, InputError>,
+    parts: &[usize],
+    answers: &Answers,
+) {
+    for part in parts {
+        let expected = answers.get(day, *part);
+        let result = match input {
+            Ok(input) => process_lines(input, day, *part),
+            Err(e) => Err(anyhow!("{}", describe_input_error(e))),
+        };
+        // an input not downloaded yet is only an error once its answer is known
+        let status = match input {
+            Err(InputError::NotFound(_)) if expected.is_none() => Some(Status::Missing),
+            _ => status(expected, &result),
+        };
+        if let Some(status) = status {
+            rows.push(Row {
+                day,
+                input: label.to_string(),
+                part: *part,
+                status,
+                answer: match result {
+                    Ok(answer) => answer.to_string(),
+                    Err(e) => format!("{:#}", e),
+                },
+                expected: expected.map(|e| e.to_string()).unwrap_or_default(),
+            });
+        }
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let width = rows
+        .iter()
+        .map(|row| row.answer.len())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or_default();
+    println!(
+        "Day  {:<10}  Part  {:<7}  {:<width$}  Expected",
+        "Input",
+        "Status",
+        "Answer",
+        width = width
+    );
+    for row in rows {
+        let line = format!(
+            "{:>3}  {:<10}  {:>4}  {:<7}  {:<width$}  {}",
+            row.day,
+            row.input,
+            row.part,
+            row.status.label(),
+            row.answer,
+            row.expected,
+            width = width
+        );
+        println!("{}", line.trim_end());
+    }
+    let count = |status: Status| rows.iter().filter(|row| row.status == status).count();
+    println!(
+        "\n{} passed, {} failed, {} errors, {} missing",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Error),
+        count(Status::Missing)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let answer = Answer::from(42_u64);
+        assert_eq!(
+            Some(Status::Pass),
+            status(Some(&answer), &Ok(42_i64.into()))
+        );
+        assert_eq!(
+            Some(Status::Fail),
+            status(Some(&answer), &Ok(41_u64.into()))
+        );
+        assert_eq!(Some(Status::Missing), status(None, &Ok(41_u64.into())));
+        assert_eq!(
+            Some(Status::Error),
+            status(Some(&answer), &Err(AocError::EmptyInput.into()))
+        );
+    }
+
+    #[test]
+    fn test_unknown_part_is_only_reported_when_expected() {
+        let unknown = || Err(AocError::UnknownPart { day: 10, part: 2 }.into());
+        assert_eq!(None, status(None, &unknown()));
+        assert_eq!(
+            Some(Status::Error),
+            status(Some(&Answer::from(1_u64)), &unknown())
+        );
+        assert!(Status::Error.is_regression());
+        assert!(!Status::Missing.is_regression());
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let not_found = Err(InputError::NotFound("inputs/day_01.txt".into()));
+        let mut answers = Answers::default();
+        answers.insert(1, 2, Answer::from(281_u64));
+        let mut rows = vec![];
+        check_parts(&mut rows, 1, "input", &not_found, &[1, 2], &answers);
+        let statuses: Vec<&Status> = rows.iter().map(|row| &row.status).collect();
+        assert_eq!(vec![&Status::Missing, &Status::Error], statuses);
+    }
+}